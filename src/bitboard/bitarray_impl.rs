@@ -1,24 +1,50 @@
 
 macro_rules! impl_bit_array_for_primitive_int {
     ($typ:ty) => {
-        impl const $crate::bitboard::BitArray for $typ {
-            fn new_zero(min_length: usize) -> Self {
-                if min_length > <$typ>::BITS as usize {
-                    panic!("min_length is too long!");
-                }
+        const_impl! {
+            impl crate::bitboard::BitArray for $typ {
+                const MAX_LENGTH: usize = <$typ>::BITS as usize;
+
+                fn new_zero(min_length: usize) -> Self {
+                    if min_length > <$typ>::BITS as usize {
+                        panic!("min_length is too long!");
+                    }
         
-                0 as $typ
-            }
+                    0 as $typ
+                }
+
+                fn new_ones(length: usize) -> Self {
+                    if length > <$typ>::BITS as usize {
+                        panic!("length is too long!");
+                    } else if length == <$typ>::BITS as usize {
+                        <$typ>::MAX
+                    } else {
+                        ((1 as $typ) << length) - 1
+                    }
+                }
         
-            fn bit_get(&self, index: usize) -> bool {
-                *self & ((1 as $typ) << index) != 0
-            }
+                fn bit_get(&self, index: usize) -> bool {
+                    *self & ((1 as $typ) << index) != 0
+                }
         
-            fn bit_set(&mut self, index: usize, value: bool) {
-                if value {
-                    *self |= (1 as $typ) << index;
-                } else {
-                    *self &= !((1 as $typ) << index);
+                fn bit_set(&mut self, index: usize, value: bool) {
+                    if value {
+                        *self |= (1 as $typ) << index;
+                    } else {
+                        *self &= !((1 as $typ) << index);
+                    }
+                }
+
+                fn is_zero(&self) -> bool {
+                    *self == 0
+                }
+
+                fn count_ones(&self) -> u32 {
+                    <$typ>::count_ones(*self)
+                }
+
+                fn trailing_zeros(&self) -> u32 {
+                    <$typ>::trailing_zeros(*self)
                 }
             }
         }
@@ -30,4 +56,418 @@ impl_bit_array_for_primitive_int!(u8);
 impl_bit_array_for_primitive_int!(u16);
 impl_bit_array_for_primitive_int!(u32);
 impl_bit_array_for_primitive_int!(u64);
-impl_bit_array_for_primitive_int!(u128);
\ No newline at end of file
+impl_bit_array_for_primitive_int!(u128);
+
+/// Get the number of 64-bit words needed to store `bits` bits
+pub const fn word_count(bits: usize) -> usize {
+    bits.div_ceil(u64::BITS as usize)
+}
+
+// Operations shared by the multi-word bit arrays, bit `i` is stored in word `i / 64`.
+
+const fn words_fill_ones(words: &mut [u64], length: usize) {
+    let mut i = 0;
+    while i < words.len() {
+        let word_start = i * u64::BITS as usize;
+        words[i] = if length >= word_start + u64::BITS as usize {
+            u64::MAX
+        } else if length > word_start {
+            (1u64 << (length - word_start)) - 1
+        } else {
+            0
+        };
+        i += 1;
+    }
+}
+
+const fn words_bit_get(words: &[u64], index: usize) -> bool {
+    words[index / u64::BITS as usize] & (1u64 << (index % u64::BITS as usize)) != 0
+}
+
+const fn words_bit_set(words: &mut [u64], index: usize, value: bool) {
+    let word = &mut words[index / u64::BITS as usize];
+    if value {
+        *word |= 1u64 << (index % u64::BITS as usize);
+    } else {
+        *word &= !(1u64 << (index % u64::BITS as usize));
+    }
+}
+
+const fn words_is_zero(words: &[u64]) -> bool {
+    let mut i = 0;
+    while i < words.len() {
+        if words[i] != 0 {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn words_count_ones(words: &[u64]) -> u32 {
+    let mut count = 0;
+    let mut i = 0;
+    while i < words.len() {
+        count += words[i].count_ones();
+        i += 1;
+    }
+    count
+}
+
+const fn words_trailing_zeros(words: &[u64]) -> u32 {
+    let mut i = 0;
+    while i < words.len() {
+        if words[i] != 0 {
+            return i as u32 * u64::BITS + words[i].trailing_zeros();
+        }
+        i += 1;
+    }
+    words.len() as u32 * u64::BITS
+}
+
+const fn words_shl(words: &mut [u64], rhs: usize) {
+    let word_shift = rhs / u64::BITS as usize;
+    let bit_shift = rhs % u64::BITS as usize;
+
+    let mut i = words.len();
+    while i > 0 {
+        i -= 1;
+        words[i] = if i < word_shift {
+            0
+        } else if bit_shift == 0 || i == word_shift {
+            words[i - word_shift] << bit_shift
+        } else {
+            (words[i - word_shift] << bit_shift) | (words[i - word_shift - 1] >> (u64::BITS as usize - bit_shift))
+        };
+    }
+}
+
+const fn words_shr(words: &mut [u64], rhs: usize) {
+    let word_shift = rhs / u64::BITS as usize;
+    let bit_shift = rhs % u64::BITS as usize;
+
+    let mut i = 0;
+    while i < words.len() {
+        words[i] = if i + word_shift >= words.len() {
+            0
+        } else if bit_shift == 0 || i + word_shift + 1 == words.len() {
+            words[i + word_shift] >> bit_shift
+        } else {
+            (words[i + word_shift] >> bit_shift) | (words[i + word_shift + 1] << (u64::BITS as usize - bit_shift))
+        };
+        i += 1;
+    }
+}
+
+const fn words_not(words: &mut [u64]) {
+    let mut i = 0;
+    while i < words.len() {
+        words[i] = !words[i];
+        i += 1;
+    }
+}
+
+/// A fixed-size bit array made of `N` 64-bit words, for boards too large to fit in a `u128`.
+///
+/// Bit `i` is stored in word `i / 64`, so shifting left moves bits towards higher words.
+/// Unlike primitive integers, shifting by more than the array's length clears it instead of panicking.
+///
+/// Use [`word_count`] to pick `N` for a board, e.g. `BitBoard<StaticGeometry<20, 20>, WordArray<{ word_count(400) }>>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WordArray<const N: usize>(pub [u64; N]);
+
+impl<const N: usize> WordArray<N> {
+    /// The total number of bits in this array
+    pub const BITS: usize = N * u64::BITS as usize;
+}
+
+const_impl! {
+    impl<const N: usize> crate::bitboard::BitArray for WordArray<N> {
+        const MAX_LENGTH: usize = Self::BITS;
+
+        fn new_zero(min_length: usize) -> Self {
+            if min_length > Self::BITS {
+                panic!("min_length is too long!");
+            }
+
+            WordArray([0; N])
+        }
+
+        fn new_ones(length: usize) -> Self {
+            if length > Self::BITS {
+                panic!("length is too long!");
+            }
+
+            let mut words = [0u64; N];
+            words_fill_ones(&mut words, length);
+            WordArray(words)
+        }
+
+        fn bit_get(&self, index: usize) -> bool {
+            words_bit_get(&self.0, index)
+        }
+
+        fn bit_set(&mut self, index: usize, value: bool) {
+            words_bit_set(&mut self.0, index, value)
+        }
+
+        fn is_zero(&self) -> bool {
+            words_is_zero(&self.0)
+        }
+
+        fn count_ones(&self) -> u32 {
+            words_count_ones(&self.0)
+        }
+
+        fn trailing_zeros(&self) -> u32 {
+            words_trailing_zeros(&self.0)
+        }
+    }
+}
+
+const_impl! {
+    impl<const N: usize> std::ops::ShlAssign<usize> for WordArray<N> {
+        fn shl_assign(&mut self, rhs: usize) {
+            words_shl(&mut self.0, rhs)
+        }
+    }
+}
+
+const_impl! {
+    impl<const N: usize> std::ops::ShrAssign<usize> for WordArray<N> {
+        fn shr_assign(&mut self, rhs: usize) {
+            words_shr(&mut self.0, rhs)
+        }
+    }
+}
+
+const_impl! {
+    impl<const N: usize> std::ops::Shl<usize> for WordArray<N> {
+        type Output = Self;
+
+        fn shl(mut self, rhs: usize) -> Self::Output {
+            self <<= rhs;
+            self
+        }
+    }
+}
+
+const_impl! {
+    impl<const N: usize> std::ops::Shr<usize> for WordArray<N> {
+        type Output = Self;
+
+        fn shr(mut self, rhs: usize) -> Self::Output {
+            self >>= rhs;
+            self
+        }
+    }
+}
+
+const_impl! {
+    impl<const N: usize> std::ops::Not for WordArray<N> {
+        type Output = Self;
+
+        fn not(mut self) -> Self::Output {
+            words_not(&mut self.0);
+            self
+        }
+    }
+}
+
+macro_rules! impl_word_array_binary_op {
+    ($op:ident, $op_fn:ident, $assign_op:ident, $assign_op_fn:ident, $word_op:tt) => {
+        const_impl! {
+            impl<const N: usize> std::ops::$assign_op for WordArray<N> {
+                fn $assign_op_fn(&mut self, rhs: Self) {
+                    let mut i = 0;
+                    while i < N {
+                        self.0[i] = self.0[i] $word_op rhs.0[i];
+                        i += 1;
+                    }
+                }
+            }
+        }
+
+        const_impl! {
+            impl<const N: usize> std::ops::$op for WordArray<N> {
+                type Output = Self;
+
+                fn $op_fn(mut self, rhs: Self) -> Self::Output {
+                    std::ops::$assign_op::$assign_op_fn(&mut self, rhs);
+                    self
+                }
+            }
+        }
+    };
+}
+
+impl_word_array_binary_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_word_array_binary_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+impl_word_array_binary_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+/// A heap allocated bit array made of 64-bit words, sized at runtime by [`BitArray::new_zero`](crate::bitboard::BitArray::new_zero).
+///
+/// This pairs with [`DynGeometry`](crate::bitboard::DynGeometry) for boards of any size.
+/// Shifts keep the number of words fixed, bits shifted past the last word are dropped.
+/// Binary operations between arrays of different lengths treat the missing words as 0, and grow the left side to fit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WordVec(pub Vec<u64>);
+
+impl crate::bitboard::BitArray for WordVec {
+    const MAX_LENGTH: usize = usize::MAX;
+
+    fn new_zero(min_length: usize) -> Self {
+        WordVec(vec![0; word_count(min_length)])
+    }
+
+    fn new_ones(length: usize) -> Self {
+        let mut words = vec![0; word_count(length)];
+        words_fill_ones(&mut words, length);
+        WordVec(words)
+    }
+
+    fn bit_get(&self, index: usize) -> bool {
+        words_bit_get(&self.0, index)
+    }
+
+    fn bit_set(&mut self, index: usize, value: bool) {
+        words_bit_set(&mut self.0, index, value)
+    }
+
+    fn is_zero(&self) -> bool {
+        words_is_zero(&self.0)
+    }
+
+    fn count_ones(&self) -> u32 {
+        words_count_ones(&self.0)
+    }
+
+    fn trailing_zeros(&self) -> u32 {
+        words_trailing_zeros(&self.0)
+    }
+}
+
+impl std::ops::ShlAssign<usize> for WordVec {
+    fn shl_assign(&mut self, rhs: usize) {
+        words_shl(&mut self.0, rhs)
+    }
+}
+
+impl std::ops::ShrAssign<usize> for WordVec {
+    fn shr_assign(&mut self, rhs: usize) {
+        words_shr(&mut self.0, rhs)
+    }
+}
+
+impl std::ops::Shl<usize> for WordVec {
+    type Output = Self;
+
+    fn shl(mut self, rhs: usize) -> Self::Output {
+        self <<= rhs;
+        self
+    }
+}
+
+impl std::ops::Shr<usize> for WordVec {
+    type Output = Self;
+
+    fn shr(mut self, rhs: usize) -> Self::Output {
+        self >>= rhs;
+        self
+    }
+}
+
+impl std::ops::Not for WordVec {
+    type Output = Self;
+
+    fn not(mut self) -> Self::Output {
+        words_not(&mut self.0);
+        self
+    }
+}
+
+macro_rules! impl_word_vec_binary_op {
+    ($op:ident, $op_fn:ident, $assign_op:ident, $assign_op_fn:ident, $word_op:tt) => {
+        impl std::ops::$assign_op for WordVec {
+            fn $assign_op_fn(&mut self, rhs: Self) {
+                if rhs.0.len() > self.0.len() {
+                    self.0.resize(rhs.0.len(), 0);
+                }
+
+                for (i, word) in self.0.iter_mut().enumerate() {
+                    *word = *word $word_op rhs.0.get(i).copied().unwrap_or(0);
+                }
+            }
+        }
+
+        impl std::ops::$op for WordVec {
+            type Output = Self;
+
+            fn $op_fn(mut self, rhs: Self) -> Self::Output {
+                std::ops::$assign_op::$assign_op_fn(&mut self, rhs);
+                self
+            }
+        }
+    };
+}
+
+impl_word_vec_binary_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_word_vec_binary_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+impl_word_vec_binary_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+#[cfg(test)]
+mod test {
+    use super::{word_count, WordArray, WordVec};
+    use crate::bitboard::{BitArray, BitBoard, Coordinates, DynGeometry, StaticGeometry};
+
+    #[test]
+    fn word_array_shifts_carry_across_words() {
+        let mut bits = WordArray::<3>::new_zero(192);
+        bits.bit_set(63, true);
+        bits.bit_set(100, true);
+
+        let shifted = bits << 70;
+        assert_eq!(shifted.0, [0, 0, (1 << 5) | (1 << 42)]);
+        assert_eq!(shifted >> 70, bits);
+        assert_eq!(bits >> 64, WordArray([1 << 36, 0, 0]));
+        assert!((bits << 192).is_zero());
+    }
+
+    #[test]
+    fn word_array_new_ones_only_sets_length_bits() {
+        assert_eq!(WordArray::<3>::new_ones(130).0, [u64::MAX, u64::MAX, 0b11]);
+        assert_eq!(WordArray::<2>::new_ones(128).0, [u64::MAX, u64::MAX]);
+        assert_eq!(!WordArray::<2>::new_ones(64), WordArray([0, u64::MAX]));
+    }
+
+    #[test]
+    fn standard_20x20_board_fits_in_word_array() {
+        let mut board = BitBoard::new(StaticGeometry::<20, 20>);
+        let corner = Coordinates::new(19, 19);
+        board.set(corner, true);
+        assert!(board.get(corner));
+
+        let board: BitBoard<_, WordArray<{ word_count(400) }>> = board.shift(-19, -19);
+        assert!(board.get(Coordinates::zero()));
+        assert!(!(!board).get(Coordinates::zero()));
+        assert!((!board).get(corner));
+    }
+
+    #[test]
+    fn word_vec_sizes_itself_for_any_dynamic_board() {
+        let geometry = DynGeometry::new(37, 23);
+        let mut board: BitBoard<_, WordVec> = BitBoard::new(geometry);
+        assert_eq!(board.data().0.len(), word_count(37 * 23));
+
+        let corner = Coordinates::new(36, 22);
+        board.set(corner, true);
+        board.shift_in_place(-36, -22);
+        assert!(board.get(Coordinates::zero()));
+        assert!(!board.get(corner));
+
+        let inverted = !board.clone();
+        assert!(inverted.get(corner));
+        assert_eq!(inverted.clone() | board.clone(), BitBoard::new_full(geometry));
+        assert!((inverted & board).is_empty());
+    }
+}
@@ -4,7 +4,7 @@ use std::{
     ops::{Add, AddAssign},
 };
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Coordinates {
     /// zero-based row number
     pub y: i16,
@@ -33,13 +33,15 @@ impl Coordinates {
     }
 }
 
-impl const Add for Coordinates {
-    type Output = Self;
+const_impl! {
+    impl Add for Coordinates {
+        type Output = Self;
 
-    fn add(mut self, rhs: Self) -> Self::Output {
-        self.x = self.x + rhs.x;
-        self.y = self.y + rhs.y;
-        self
+        fn add(mut self, rhs: Self) -> Self::Output {
+            self.x += rhs.x;
+            self.y += rhs.y;
+            self
+        }
     }
 }
 
@@ -50,13 +52,15 @@ impl AddAssign<Self> for Coordinates {
     }
 }
 
-impl<'a> const Add<&'a Self> for Coordinates {
-    type Output = Self;
+const_impl! {
+    impl<'a> Add<&'a Self> for Coordinates {
+        type Output = Self;
 
-    fn add(mut self, rhs: &'a Self) -> Self::Output {
-        self.x = self.x + rhs.x;
-        self.y = self.y + rhs.y;
-        self
+        fn add(mut self, rhs: &'a Self) -> Self::Output {
+            self.x += rhs.x;
+            self.y += rhs.y;
+            self
+        }
     }
 }
 
@@ -90,7 +94,7 @@ pub enum Direction {
     /// Row: 0, Column: -1
     West = 0b0000_1000,
 
-    /// Row: 1, Column: 1
+    /// Row: -1, Column: 1
     NorthEast = 0b0001_0000,
 
     /// Row: -1, Column: -1
@@ -130,10 +134,10 @@ impl Direction {
             Self::South => Coordinates::new(0, 1),
             Self::East => Coordinates::new(1, 0),
             Self::West => Coordinates::new(-1, 0),
-            Self::NorthEast => Coordinates::new(1, 1),
+            Self::NorthEast => Coordinates::new(1, -1),
             Self::NorthWest => Coordinates::new(-1, -1),
             Self::SouthEast => Coordinates::new(1, 1),
-            Self::SouthWest => Coordinates::new(1, -1),
+            Self::SouthWest => Coordinates::new(-1, 1),
         }
     }
 
@@ -153,3 +157,21 @@ impl Direction {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Coordinates, Direction};
+
+    #[test]
+    fn diagonal_offsets_combine_their_components() {
+        assert_eq!(Direction::NorthEast.as_coordinates(), Coordinates::new(1, -1));
+        assert_eq!(Direction::NorthWest.as_coordinates(), Coordinates::new(-1, -1));
+        assert_eq!(Direction::SouthEast.as_coordinates(), Coordinates::new(1, 1));
+        assert_eq!(Direction::SouthWest.as_coordinates(), Coordinates::new(-1, 1));
+
+        for diagonal in Direction::DIAGONAL {
+            let sum: Coordinates = diagonal.components().iter().map(|dir| dir.as_coordinates()).sum();
+            assert_eq!(diagonal.as_coordinates(), sum);
+        }
+    }
+}
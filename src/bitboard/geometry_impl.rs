@@ -1,18 +1,22 @@
 use super::BoardGeometry;
 
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct StaticGeometry<const WIDTH: i16, const HEIGHT: i16>;
 
-impl<const WIDTH: i16, const HEIGHT: i16> const BoardGeometry for StaticGeometry<WIDTH,  HEIGHT> {
-    fn width(&self) -> i16 {
-        WIDTH
-    }
+const_impl! {
+    impl<const WIDTH: i16, const HEIGHT: i16> BoardGeometry for StaticGeometry<WIDTH, HEIGHT> {
+        fn width(&self) -> i16 {
+            WIDTH
+        }
 
-    fn height(&self) -> i16 {
-        HEIGHT
+        fn height(&self) -> i16 {
+            HEIGHT
+        }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct DynGeometry {
     pub width: i16,
     pub height: i16,
@@ -27,12 +31,14 @@ impl DynGeometry {
     }
 }
 
-impl const BoardGeometry for DynGeometry {
-    fn width(&self) -> i16 {
-        self.width
-    }
+const_impl! {
+    impl BoardGeometry for DynGeometry {
+        fn width(&self) -> i16 {
+            self.width
+        }
 
-    fn height(&self) -> i16 {
-        self.height
+        fn height(&self) -> i16 {
+            self.height
+        }
     }
 }
\ No newline at end of file
@@ -2,29 +2,47 @@ mod bitarray_impl;
 mod coordinates;
 mod geometry_impl;
 mod traits;
+mod transform;
 
-#[allow(
-    unused,
-    reason = "currently there's nothing being re-exported, but that may change in the future"
-)]
 pub use bitarray_impl::*;
 pub use coordinates::*;
 pub use geometry_impl::*;
 pub use traits::*;
+pub use transform::*;
+
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
 
 const BITBOARD_VALIDATE_ALL_COORDINATES: bool = true;
 
 /// A 2D array of boolean values, typically used to store some information about a GridGame
-pub struct BitBoard<GeomT: const BoardGeometry, BitsT: const BitArray> {
+///
+/// Bits past the last cell in `data` are kept at 0 by every operation on the board, so boards can be compared and combined directly.
+/// [`BitBoard::new_with_data`] stores its data as given, use [`BitBoard::try_new_with_data`] for data that isn't known to be clean.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitBoard<GeomT: BoardGeometry, BitsT: BitArray> {
     data: BitsT,
     geometry: GeomT,
 }
 
-impl<GeomT: const BoardGeometry, BitsT: const BitArray> BitBoard<GeomT, BitsT> {
+impl<GeomT: BoardGeometry, BitsT: BitArray> BitBoard<GeomT, BitsT> {
+    /// Create a board from its underlying bit array.
+    ///
+    /// `data` must not have any bits set past the last cell, otherwise comparisons, [`BitBoard::count_ones`]
+    /// and [`BitBoard::is_empty`] will count them.
     pub const fn new_with_data(geometry: GeomT, data: BitsT) -> Self {
         Self { data, geometry }
     }
 
+    /// Create a board from its underlying bit array, or return `None` if `data` has bits set past the last cell
+    pub fn try_new_with_data(geometry: GeomT, data: BitsT) -> Option<Self> {
+        let cells = BitsT::new_ones(geometry.width() as usize * geometry.height() as usize);
+        if (data.clone() & cells).count_ones() != data.count_ones() {
+            return None;
+        }
+
+        Some(Self { data, geometry })
+    }
+
     pub fn new(geometry: GeomT) -> Self {
         Self {
             data: BitsT::new_zero(geometry.width() as usize * geometry.height() as usize),
@@ -32,19 +50,44 @@ impl<GeomT: const BoardGeometry, BitsT: const BitArray> BitBoard<GeomT, BitsT> {
         }
     }
 
-    pub const fn is_coordinate_in_bounds(&self, coord: Coordinates) -> bool {
-        coord.y >= 0
-            && coord.x >= 0
-            && coord.x < self.geometry().width()
-            && coord.y < self.geometry().height()
+    /// Create a board with every cell set
+    pub fn new_full(geometry: GeomT) -> Self {
+        Self {
+            data: BitsT::new_ones(geometry.width() as usize * geometry.height() as usize),
+            geometry,
+        }
     }
 
-    pub const fn coordinate_to_index(&self, coord: Coordinates) -> usize {
-        if BITBOARD_VALIDATE_ALL_COORDINATES {
-            assert!(self.is_coordinate_in_bounds(coord));
+    const_fn! {
+        pub fn is_coordinate_in_bounds(&self, coord: Coordinates) -> bool
+        where
+            GeomT: [const] BoardGeometry,
+        {
+            coord.y >= 0
+                && coord.x >= 0
+                && coord.x < self.geometry().width()
+                && coord.y < self.geometry().height()
         }
 
-        self.geometry().width() as usize * coord.y as usize + coord.x as usize
+        pub fn coordinate_to_index(&self, coord: Coordinates) -> usize
+        where
+            GeomT: [const] BoardGeometry,
+        {
+            if BITBOARD_VALIDATE_ALL_COORDINATES {
+                assert!(self.is_coordinate_in_bounds(coord));
+            }
+
+            self.geometry().width() as usize * coord.y as usize + coord.x as usize
+        }
+
+        /// Get the coordinates of the cell stored at `index`, the inverse of [`BitBoard::coordinate_to_index`]
+        pub fn index_to_coordinate(&self, index: usize) -> Coordinates
+        where
+            GeomT: [const] BoardGeometry,
+        {
+            let width = self.geometry().width() as usize;
+            Coordinates::new((index % width) as i16, (index / width) as i16)
+        }
     }
 
     /// Get the underlying bit array where the board state is stored
@@ -57,63 +100,311 @@ impl<GeomT: const BoardGeometry, BitsT: const BitArray> BitBoard<GeomT, BitsT> {
         &self.geometry
     }
 
-    pub const fn get(&self, coord: Coordinates) -> bool {
-        self.data().bit_get(self.coordinate_to_index(coord))
+    const_fn! {
+        pub fn get(&self, coord: Coordinates) -> bool
+        where
+            GeomT: [const] BoardGeometry,
+            BitsT: [const] BitArray,
+        {
+            self.data().bit_get(self.coordinate_to_index(coord))
+        }
     }
 
     pub fn set(&mut self, coord: Coordinates, value: bool)  {
         self.data.bit_set(self.coordinate_to_index(coord), value)
     }
 
-    /// Test the tiles surrounding `coord` in direction(s) `dir`.
-    /// Any tested tiles set to `1` will have their direction, relative to `coord`, set in the the returned bitmap.
-    pub const fn are_adjacent_tiles_set(
-        &self,
-        coord: Coordinates,
-        dir: DirectionSet,
-    ) -> DirectionSet {
-        let mut result = 0u8;
-
-        let all_dirs = Direction::all_as_array();
-        let mut i = 0;
-        while i < all_dirs.len() {
-            if (dir.bits_c() & all_dirs[i] as u8) != 0 {
-                let test_coord = coord + all_dirs[i].as_coordinates();
-                if self.is_coordinate_in_bounds(test_coord) && self.get(test_coord) {
-                    result |= all_dirs[i] as u8
+    /// Returns true if no cells are set
+    pub fn is_empty(&self) -> bool {
+        self.data.is_zero()
+    }
+
+    /// Copy this board's cells onto a board with a different geometry.
+    /// Cells stay at the same coordinates, any that fall outside the new geometry are dropped.
+    pub fn resize(&self, geometry: GeomT) -> Self {
+        let mut resized = Self::new(geometry);
+        for coord in self.iter_set() {
+            if resized.is_coordinate_in_bounds(coord) {
+                resized.set(coord, true);
+            }
+        }
+        resized
+    }
+
+    const_fn! {
+        /// Count the number of set cells
+        pub fn count_ones(&self) -> u32
+        where
+            BitsT: [const] BitArray,
+        {
+            self.data().count_ones()
+        }
+
+        /// Get the first set cell, scanning rows top to bottom and each row left to right
+        pub fn first_set(&self) -> Option<Coordinates>
+        where
+            GeomT: [const] BoardGeometry,
+            BitsT: [const] BitArray,
+        {
+            let index = self.data().trailing_zeros() as usize;
+            if index < self.geometry().width() as usize * self.geometry().height() as usize {
+                Some(self.index_to_coordinate(index))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Iterate over the coordinates of every set cell, in the same order as [`BitBoard::first_set`]
+    pub fn iter_set(&self) -> BitBoardCells<BitsT> {
+        BitBoardCells {
+            bits: self.data.clone(),
+            width: self.geometry().width(),
+            length: self.geometry().width() as usize * self.geometry().height() as usize,
+        }
+    }
+
+    /// Iterate over the coordinates of every cell that is not set, in the same order as [`BitBoard::first_set`]
+    pub fn iter_clear(&self) -> BitBoardCells<BitsT> {
+        (!self.clone()).iter_set()
+    }
+
+    /// Returns true if any cell is set in both `self` and `other`
+    pub fn intersects(&self, other: &Self) -> bool {
+        !(self.clone() & other.clone()).is_empty()
+    }
+
+    /// Returns true if every cell set in `self` is also set in `other`
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        self.and_not(other).is_empty()
+    }
+
+    /// Get the cells that are set in `self`, but not in `other`
+    pub fn and_not(&self, other: &Self) -> Self {
+        self.clone() & !other.clone()
+    }
+
+    const_fn! {
+        /// Move every set cell `dx` columns right and `dy` rows down (or left and up, for negative offsets).
+        /// Cells that are moved past an edge of the board are dropped, they never wrap into the next row.
+        pub fn shift_in_place(&mut self, dx: i16, dy: i16)
+        where
+            GeomT: [const] BoardGeometry,
+            BitsT: [const] BitArray,
+        {
+            let width = self.geometry().width();
+            let height = self.geometry().height();
+            let length = width as usize * height as usize;
+
+            if dx.unsigned_abs() >= width as u16 || dy.unsigned_abs() >= height as u16 {
+                self.data &= BitsT::new_zero(length);
+                return;
+            }
+
+            if dx > 0 {
+                let mask = self.leading_columns_mask(width - dx);
+                self.data &= mask;
+                self.data <<= dx as usize;
+            } else if dx < 0 {
+                let mask = self.leading_columns_mask(width + dx);
+                self.data >>= -dx as usize;
+                self.data &= mask;
+            }
+
+            if dy > 0 {
+                self.data <<= dy as usize * width as usize;
+                self.data &= BitsT::new_ones(length);
+            } else if dy < 0 {
+                self.data >>= -dy as usize * width as usize;
+            }
+        }
+
+        /// Move every set cell by the given offset, see [`BitBoard::shift_in_place`].
+        pub fn shift(mut self, dx: i16, dy: i16) -> Self
+        where
+            GeomT: [const] BoardGeometry,
+            BitsT: [const] BitArray,
+        {
+            self.shift_in_place(dx, dy);
+            self
+        }
+    }
+
+    /// Get every cell that shares an edge with a set cell, but is not set itself
+    pub fn orthogonal_neighbors(&self) -> Self {
+        let neighbors = self.clone().shift(1, 0)
+            | self.clone().shift(-1, 0)
+            | self.clone().shift(0, 1)
+            | self.clone().shift(0, -1);
+        neighbors.and_not(self)
+    }
+
+    /// Get every cell that touches a set cell at a corner, but neither shares an edge with a set cell nor is set itself
+    pub fn diagonal_neighbors(&self) -> Self {
+        let neighbors = self.clone().shift(1, 1)
+            | self.clone().shift(-1, 1)
+            | self.clone().shift(1, -1)
+            | self.clone().shift(-1, -1);
+        neighbors.and_not(&(self.orthogonal_neighbors() | self.clone()))
+    }
+
+    const_fn! {
+        /// Get a bit array with every cell in the leftmost `count` columns set
+        fn leading_columns_mask(&self, count: i16) -> BitsT
+        where
+            GeomT: [const] BoardGeometry,
+            BitsT: [const] BitArray,
+        {
+            let length = self.geometry().width() as usize * self.geometry().height() as usize;
+            let mut mask = BitsT::new_zero(length);
+            if count <= 0 {
+                return mask;
+            }
+
+            let mut y = 0;
+            while y < self.geometry().height() {
+                let mut row = BitsT::new_ones(length);
+                row >>= length - count as usize;
+
+                // the first row needs no shift, and shifting by the full width overflows when it fills the array
+                if y > 0 {
+                    mask <<= self.geometry().width() as usize;
                 }
+                mask |= row;
+                y += 1;
             }
-            i += 1;
+
+            mask
         }
+    }
 
-        DirectionSet::from_bits_truncate_c(result, DirectionSet::CONST_TOKEN)
+    fn debug_assert_same_geometry(&self, other: &Self) {
+        debug_assert!(
+            self.geometry().width() == other.geometry().width()
+                && self.geometry().height() == other.geometry().height(),
+            "bit boards have different geometries"
+        );
     }
 
-    /// Test the tiles surrounding `coord` in direction(s) `dir`, treat tiles that are out of bounds as set.
-    /// Any tested tiles set to `1` will have their direction, relative to `coord`, set in the the returned bitmap.
-    pub const fn are_adjacent_tiles_set_or_out_of_bounds(
-        &self,
-        coord: Coordinates,
-        dir: DirectionSet,
-    ) -> DirectionSet {
-        let mut result = 0u8;
+    const_fn! {
+        /// Test the tiles surrounding `coord` in direction(s) `dir`.
+        /// Any tested tiles set to `1` will have their direction, relative to `coord`, set in the the returned bitmap.
+        pub fn are_adjacent_tiles_set(
+            &self,
+            coord: Coordinates,
+            dir: DirectionSet,
+        ) -> DirectionSet
+        where
+            GeomT: [const] BoardGeometry,
+            BitsT: [const] BitArray,
+        {
+            let mut result = 0u8;
+
+            let all_dirs = Direction::all_as_array();
+            let mut i = 0;
+            while i < all_dirs.len() {
+                if (dir.bits_c() & all_dirs[i] as u8) != 0 {
+                    let test_coord = coord + all_dirs[i].as_coordinates();
+                    if self.is_coordinate_in_bounds(test_coord) && self.get(test_coord) {
+                        result |= all_dirs[i] as u8
+                    }
+                }
+                i += 1;
+            }
+
+            DirectionSet::from_bits_truncate_c(result, DirectionSet::CONST_TOKEN)
+        }
+
+        /// Test the tiles surrounding `coord` in direction(s) `dir`, treat tiles that are out of bounds as set.
+        /// Any tested tiles set to `1` will have their direction, relative to `coord`, set in the the returned bitmap.
+        pub fn are_adjacent_tiles_set_or_out_of_bounds(
+            &self,
+            coord: Coordinates,
+            dir: DirectionSet,
+        ) -> DirectionSet
+        where
+            GeomT: [const] BoardGeometry,
+            BitsT: [const] BitArray,
+        {
+            let mut result = 0u8;
 
-        let all_dirs = Direction::all_as_array();
-        let mut i = 0;
-        while i < all_dirs.len() {
-            if (dir.bits_c() & all_dirs[i] as u8) != 0 {
-                let test_coord = coord + all_dirs[i].as_coordinates();
-                if !self.is_coordinate_in_bounds(test_coord) || self.get(test_coord) {
-                    result |= all_dirs[i] as u8
+            let all_dirs = Direction::all_as_array();
+            let mut i = 0;
+            while i < all_dirs.len() {
+                if (dir.bits_c() & all_dirs[i] as u8) != 0 {
+                    let test_coord = coord + all_dirs[i].as_coordinates();
+                    if !self.is_coordinate_in_bounds(test_coord) || self.get(test_coord) {
+                        result |= all_dirs[i] as u8
+                    }
                 }
+                i += 1;
             }
-            i += 1;
+
+            DirectionSet::from_bits_truncate_c(result, DirectionSet::CONST_TOKEN)
         }
+    }
+}
 
-        DirectionSet::from_bits_truncate_c(result, DirectionSet::CONST_TOKEN)
+/// An iterator over a snapshot of a board's set cells, created by [`BitBoard::iter_set`] and [`BitBoard::iter_clear`]
+pub struct BitBoardCells<BitsT: BitArray> {
+    bits: BitsT,
+    width: i16,
+    length: usize,
+}
+
+impl<BitsT: BitArray> Iterator for BitBoardCells<BitsT> {
+    type Item = Coordinates;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.bits.trailing_zeros() as usize;
+        if index >= self.length {
+            return None;
+        }
+
+        self.bits.bit_set(index, false);
+        let width = self.width as usize;
+        Some(Coordinates::new((index % width) as i16, (index / width) as i16))
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.bits.count_ones() as usize;
+        (count, Some(count))
+    }
+}
+
+macro_rules! impl_bitboard_binary_op {
+    ($op:ident, $op_fn:ident, $assign_op:ident, $assign_op_fn:ident) => {
+        impl<GeomT: BoardGeometry, BitsT: BitArray> $op for BitBoard<GeomT, BitsT> {
+            type Output = Self;
+
+            fn $op_fn(mut self, rhs: Self) -> Self::Output {
+                self.$assign_op_fn(rhs);
+                self
+            }
+        }
+
+        impl<GeomT: BoardGeometry, BitsT: BitArray> $assign_op for BitBoard<GeomT, BitsT> {
+            fn $assign_op_fn(&mut self, rhs: Self) {
+                self.debug_assert_same_geometry(&rhs);
+                self.data.$assign_op_fn(rhs.data);
+            }
+        }
+    };
 }
 
+impl_bitboard_binary_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bitboard_binary_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bitboard_binary_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl<GeomT: BoardGeometry, BitsT: BitArray> Not for BitBoard<GeomT, BitsT> {
+    type Output = Self;
+
+    /// Invert every cell on the board, bits outside the board are left unset
+    fn not(mut self) -> Self::Output {
+        self.data ^= BitsT::new_ones(self.geometry.width() as usize * self.geometry.height() as usize);
+        self
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -121,7 +412,117 @@ mod test {
 
     use crate::bitboard::Direction;
 
-    use super::{BitBoard, Coordinates, DirectionSet, StaticGeometry};
+    use super::{BitBoard, Coordinates, DirectionSet, DynGeometry, StaticGeometry, WordVec};
+
+    #[test]
+    fn not_leaves_bits_outside_the_board_unset() {
+        let board = BitBoard::new_with_data(StaticGeometry::<3, 3>, 0b000_010_000u16);
+        assert_eq!(*(!board).data(), 0b111_101_111u16);
+        assert_eq!(!!board, board);
+    }
+
+    #[test]
+    fn try_new_with_data_rejects_bits_past_the_last_cell() {
+        assert_eq!(
+            BitBoard::try_new_with_data(StaticGeometry::<3, 3>, 0b000_010_000u16),
+            Some(BitBoard::new_with_data(StaticGeometry::<3, 3>, 0b000_010_000u16))
+        );
+        assert_eq!(BitBoard::try_new_with_data(StaticGeometry::<3, 3>, 0b10_000_010_000u16), None);
+
+        let geometry = DynGeometry::new(10, 10);
+        assert!(BitBoard::try_new_with_data(geometry, WordVec(vec![u64::MAX, 0b1111])).is_some());
+        assert!(BitBoard::try_new_with_data(geometry, WordVec(vec![0, 1 << 36])).is_none());
+        assert!(BitBoard::try_new_with_data(geometry, WordVec(vec![0, 0, 1])).is_none());
+    }
+
+    #[test]
+    fn shift_drops_cells_instead_of_wrapping() {
+        let board = BitBoard::new_with_data(StaticGeometry::<3, 3>, 0b001_100_110u16);
+
+        assert_eq!(*board.shift(1, 0).data(), 0b010_000_100u16);
+        assert_eq!(*board.shift(-1, 0).data(), 0b000_010_011u16);
+        assert_eq!(*board.shift(0, 1).data(), 0b100_110_000u16);
+        assert_eq!(*board.shift(0, -1).data(), 0b000_001_100u16);
+        assert_eq!(*board.shift(-1, 1).data(), 0b010_011_000u16);
+        assert!(board.shift(3, 0).is_empty());
+        assert!(board.shift(0, -3).is_empty());
+    }
+
+    #[test]
+    fn shift_board_as_wide_as_its_bit_array() {
+        let board = BitBoard::new_with_data(StaticGeometry::<8, 1>, 0b1000_0001u8);
+        assert_eq!(*board.shift(1, 0).data(), 0b0000_0010u8);
+        assert_eq!(*board.shift(-1, 0).data(), 0b0100_0000u8);
+
+        let board = BitBoard::new_with_data(StaticGeometry::<16, 1>, 0b0100u16);
+        assert_eq!(*board.orthogonal_neighbors().data(), 0b1010u16);
+    }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn shift_is_usable_in_const_context() {
+        const SHIFTED: BitBoard<StaticGeometry<4, 2>, u8> =
+            BitBoard::new_with_data(StaticGeometry::<4, 2>, 0b0000_1111u8).shift(2, 1);
+        assert_eq!(*SHIFTED.data(), 0b1100_0000u8);
+    }
+
+    #[test]
+    fn iterate_set_and_clear_cells_in_row_major_order() {
+        let board = BitBoard::new_with_data(StaticGeometry::<3, 2>, 0b100_010u8);
+
+        assert_eq!(board.count_ones(), 2);
+        assert_eq!(board.first_set(), Some(Coordinates::new(1, 0)));
+        assert_eq!(
+            board.iter_set().collect::<Vec<_>>(),
+            vec![Coordinates::new(1, 0), Coordinates::new(2, 1)]
+        );
+        assert_eq!(
+            board.iter_clear().collect::<Vec<_>>(),
+            vec![
+                Coordinates::new(0, 0),
+                Coordinates::new(2, 0),
+                Coordinates::new(0, 1),
+                Coordinates::new(1, 1)
+            ]
+        );
+        assert_eq!(BitBoard::<_, u8>::new(StaticGeometry::<3, 2>).first_set(), None);
+    }
+
+    #[test]
+    fn neighbors_of_single_cell() {
+        let center = BitBoard::new_with_data(StaticGeometry::<3, 3>, 0b000_010_000u16);
+        assert_eq!(*center.orthogonal_neighbors().data(), 0b010_101_010u16);
+        assert_eq!(*center.diagonal_neighbors().data(), 0b101_000_101u16);
+
+        // the right edge must not leak into the next row's left edge
+        let edge = BitBoard::new_with_data(StaticGeometry::<4, 3>, 0b0000_1000_0000u16);
+        assert_eq!(*edge.orthogonal_neighbors().data(), 0b1000_0100_1000u16);
+        assert_eq!(*edge.diagonal_neighbors().data(), 0b0100_0000_0100u16);
+    }
+
+    #[test]
+    fn diagonal_neighbors_exclude_cells_touching_an_edge() {
+        // x x .
+        // . . .
+        let domino = BitBoard::new_with_data(StaticGeometry::<3, 2>, 0b000_011u8);
+        assert_eq!(*domino.orthogonal_neighbors().data(), 0b011_100u8);
+        assert_eq!(*domino.diagonal_neighbors().data(), 0b100_000u8);
+    }
+
+    #[test]
+    fn subset_and_intersection_of_3x3_boards() {
+        let row = BitBoard::new_with_data(StaticGeometry::<3, 3>, 0b000_111_000u16);
+        let center = BitBoard::new_with_data(StaticGeometry::<3, 3>, 0b000_010_000u16);
+        let corners = BitBoard::new_with_data(StaticGeometry::<3, 3>, 0b101_000_101u16);
+
+        assert!(center.is_subset_of(&row));
+        assert!(!row.is_subset_of(&center));
+        assert!(row.intersects(&center));
+        assert!(!row.intersects(&corners));
+        assert_eq!(*row.and_not(&center).data(), 0b000_101_000u16);
+        assert!((row & corners).is_empty());
+        assert_eq!(row | corners, !BitBoard::new_with_data(StaticGeometry::<3, 3>, 0b010_000_010u16));
+    }
 
     #[test]
     fn are_adjacent_tile_set_correct_for_1x1_in_center_of_3x3() {
@@ -1,32 +1,49 @@
-use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Shl, ShlAssign, Shr, ShrAssign};
-
-/// The backing store for a bit board
-#[const_trait]
-pub trait BitArray:
-    ShlAssign
-    + ShrAssign
-    + BitAndAssign
-    + BitOrAssign
-    + BitXorAssign
-    + Shl<Self>
-    + Shr<Self>
-    + BitAnd<Self>
-    + BitOr<Self>
-    + BitXor<Self>
-    + Sized
-{
-    /// Create a new instance of the bit array with all bits set to 0
-    fn new_zero(min_length: usize) -> Self;
-
-    fn bit_get(&self, index: usize) -> bool;
-    fn bit_set(&mut self, index: usize, value: bool);
-}
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, ShlAssign, Shr, ShrAssign};
+
+const_trait! {
+    /// The backing store for a bit board
+    pub trait BitArray:
+        [const] ShlAssign<usize>
+        + [const] ShrAssign<usize>
+        + [const] BitAndAssign
+        + [const] BitOrAssign
+        + [const] BitXorAssign
+        + [const] Shl<usize, Output = Self>
+        + [const] Shr<usize, Output = Self>
+        + [const] BitAnd<Self, Output = Self>
+        + [const] BitOr<Self, Output = Self>
+        + [const] BitXor<Self, Output = Self>
+        + [const] Not<Output = Self>
+        + Clone
+        + Sized
+    {
+        /// The largest length that can be passed to [`BitArray::new_zero`] or [`BitArray::new_ones`]
+        const MAX_LENGTH: usize;
+
+        /// Create a new instance of the bit array with all bits set to 0
+        fn new_zero(min_length: usize) -> Self;
+
+        /// Create a new instance of the bit array with the first `length` bits set to 1, and all others set to 0
+        fn new_ones(length: usize) -> Self;
 
+        fn bit_get(&self, index: usize) -> bool;
+        fn bit_set(&mut self, index: usize, value: bool);
 
-/// Describes the size of a rectangular board
+        /// Returns true if no bits are set
+        fn is_zero(&self) -> bool;
+
+        /// Count the number of bits set to 1
+        fn count_ones(&self) -> u32;
+
+        /// Get the index of the lowest bit set to 1, or the total number of bits in the array if none are set
+        fn trailing_zeros(&self) -> u32;
+    }
+}
 
-#[const_trait]
-pub trait BoardGeometry {
-    fn width(&self) -> i16;
-    fn height(&self) -> i16;
+const_trait! {
+    /// Describes the size of a rectangular board
+    pub trait BoardGeometry: Copy {
+        fn width(&self) -> i16;
+        fn height(&self) -> i16;
+    }
 }
@@ -0,0 +1,244 @@
+use super::{BitArray, BitBoard, BoardGeometry, Coordinates, DynGeometry};
+
+/// One of the 8 symmetries of a rectangle (the dihedral group D4).
+///
+/// Every transform is stored as an optional transpose, followed by an optional horizontal flip, then an optional vertical flip.
+/// The discriminant encodes those steps as `0b(transpose)(vertical)(horizontal)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Transform {
+    Identity = 0b000,
+
+    /// Mirror columns, left becomes right
+    FlipHorizontal = 0b001,
+
+    /// Mirror rows, top becomes bottom
+    FlipVertical = 0b010,
+
+    Rotate180 = 0b011,
+
+    /// Swap rows and columns, mirroring along the top-left to bottom-right diagonal
+    Transpose = 0b100,
+
+    RotateClockwise = 0b101,
+
+    RotateCounterClockwise = 0b110,
+
+    /// Mirror along the top-right to bottom-left diagonal
+    AntiTranspose = 0b111,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Self::Identity,
+        Self::FlipHorizontal,
+        Self::FlipVertical,
+        Self::Rotate180,
+        Self::Transpose,
+        Self::RotateClockwise,
+        Self::RotateCounterClockwise,
+        Self::AntiTranspose,
+    ];
+
+    const FLIP_HORIZONTAL_BIT: u8 = 0b001;
+    const FLIP_VERTICAL_BIT: u8 = 0b010;
+    const TRANSPOSE_BIT: u8 = 0b100;
+
+    const fn from_bits(bits: u8) -> Transform {
+        Self::ALL[(bits & 0b111) as usize]
+    }
+
+    /// True if this transform swaps a board's width and height
+    pub const fn swaps_dimensions(self) -> bool {
+        self as u8 & Self::TRANSPOSE_BIT != 0
+    }
+
+    /// Get the transform that undoes this one
+    pub const fn inverse(self) -> Transform {
+        let m = self.matrix();
+        Self::from_matrix([[m[0][0], m[1][0]], [m[0][1], m[1][1]]])
+    }
+
+    /// Get the transform equivalent to applying `self`, then `next`
+    pub const fn then(self, next: Transform) -> Transform {
+        let a = self.matrix();
+        let b = next.matrix();
+        Self::from_matrix([
+            [
+                b[0][0] * a[0][0] + b[0][1] * a[1][0],
+                b[0][0] * a[0][1] + b[0][1] * a[1][1],
+            ],
+            [
+                b[1][0] * a[0][0] + b[1][1] * a[1][0],
+                b[1][0] * a[0][1] + b[1][1] * a[1][1],
+            ],
+        ])
+    }
+
+    /// Find where `coord`, on a `width` by `height` board, ends up after this transform
+    pub const fn apply_to_coordinates(self, coord: Coordinates, width: i16, height: i16) -> Coordinates {
+        let (mut x, mut y, mut width, mut height) = (coord.x, coord.y, width, height);
+        let bits = self as u8;
+        if bits & Self::TRANSPOSE_BIT != 0 {
+            (x, y, width, height) = (y, x, height, width);
+        }
+        if bits & Self::FLIP_HORIZONTAL_BIT != 0 {
+            x = width - 1 - x;
+        }
+        if bits & Self::FLIP_VERTICAL_BIT != 0 {
+            y = height - 1 - y;
+        }
+
+        Coordinates::new(x, y)
+    }
+
+    /// The transform as a signed permutation matrix acting on `(x, y)` column vectors
+    const fn matrix(self) -> [[i8; 2]; 2] {
+        let bits = self as u8;
+        let sx = if bits & Self::FLIP_HORIZONTAL_BIT != 0 { -1 } else { 1 };
+        let sy = if bits & Self::FLIP_VERTICAL_BIT != 0 { -1 } else { 1 };
+        if bits & Self::TRANSPOSE_BIT != 0 {
+            [[0, sx], [sy, 0]]
+        } else {
+            [[sx, 0], [0, sy]]
+        }
+    }
+
+    const fn from_matrix(m: [[i8; 2]; 2]) -> Transform {
+        let (transpose, sx, sy) = if m[0][0] != 0 {
+            (0, m[0][0], m[1][1])
+        } else {
+            (Self::TRANSPOSE_BIT, m[0][1], m[1][0])
+        };
+
+        let mut bits = transpose;
+        if sx < 0 {
+            bits |= Self::FLIP_HORIZONTAL_BIT;
+        }
+        if sy < 0 {
+            bits |= Self::FLIP_VERTICAL_BIT;
+        }
+        Self::from_bits(bits)
+    }
+}
+
+impl<BitsT: BitArray> BitBoard<DynGeometry, BitsT> {
+    const_fn! {
+        /// Create a new board with every cell moved according to `transform`.
+        /// The width and height of the result are swapped if the transform requires it.
+        pub fn transform(&self, transform: Transform) -> Self
+        where
+            BitsT: [const] BitArray,
+        {
+            let width = self.geometry().width();
+            let height = self.geometry().height();
+            let geometry = if transform.swaps_dimensions() {
+                DynGeometry::new(height, width)
+            } else {
+                DynGeometry::new(width, height)
+            };
+
+            let mut data = BitsT::new_zero(width as usize * height as usize);
+            let mut coord = Coordinates::zero();
+            while coord.y < height {
+                while coord.x < width {
+                    if self.get(coord) {
+                        let dest = transform.apply_to_coordinates(coord, width, height);
+                        data.bit_set(geometry.width() as usize * dest.y as usize + dest.x as usize, true);
+                    }
+                    coord.x += 1;
+                }
+                coord.x = 0;
+                coord.y += 1;
+            }
+
+            BitBoard::new_with_data(geometry, data)
+        }
+
+        pub fn rotate_clockwise(&self) -> Self
+        where
+            BitsT: [const] BitArray,
+        {
+            self.transform(Transform::RotateClockwise)
+        }
+
+        pub fn rotate_counter_clockwise(&self) -> Self
+        where
+            BitsT: [const] BitArray,
+        {
+            self.transform(Transform::RotateCounterClockwise)
+        }
+
+        pub fn rotate_180(&self) -> Self
+        where
+            BitsT: [const] BitArray,
+        {
+            self.transform(Transform::Rotate180)
+        }
+
+        pub fn flip_horizontal(&self) -> Self
+        where
+            BitsT: [const] BitArray,
+        {
+            self.transform(Transform::FlipHorizontal)
+        }
+
+        pub fn flip_vertical(&self) -> Self
+        where
+            BitsT: [const] BitArray,
+        {
+            self.transform(Transform::FlipVertical)
+        }
+
+        pub fn transpose(&self) -> Self
+        where
+            BitsT: [const] BitArray,
+        {
+            self.transform(Transform::Transpose)
+        }
+
+        pub fn anti_transpose(&self) -> Self
+        where
+            BitsT: [const] BitArray,
+        {
+            self.transform(Transform::AntiTranspose)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Transform;
+    use crate::bitboard::{BitBoard, DynGeometry};
+
+    #[test]
+    fn rotate_clockwise_3x2_l() {
+        // x x x
+        // x . .
+        let board = BitBoard::new_with_data(DynGeometry::new(3, 2), 0b001_111u8);
+
+        // x x
+        // . x
+        // . x
+        let rotated = board.rotate_clockwise();
+        assert_eq!(*rotated.geometry(), DynGeometry::new(2, 3));
+        assert_eq!(*rotated.data(), 0b10_10_11u8);
+        assert_eq!(rotated.rotate_counter_clockwise(), board);
+    }
+
+    #[test]
+    fn composed_transforms_match_applying_each_in_turn() {
+        let board = BitBoard::new_with_data(DynGeometry::new(3, 2), 0b011_101u8);
+        for a in Transform::ALL {
+            assert_eq!(a.then(a.inverse()), Transform::Identity);
+            assert_eq!(board.transform(a).transform(a.inverse()), board);
+            for b in Transform::ALL {
+                assert_eq!(
+                    board.transform(a).transform(b),
+                    board.transform(a.then(b)),
+                    "{a:?} then {b:?}"
+                );
+            }
+        }
+    }
+}
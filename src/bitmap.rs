@@ -0,0 +1,441 @@
+use std::{collections::HashMap, fmt, hash::Hash, str::FromStr};
+
+use crate::{
+    bitboard::{BitArray, BitBoard, BoardGeometry, Coordinates, DynGeometry, WordVec},
+    ruleset::BlokusPiece,
+};
+
+/// The state of a single tile in a [`BlokusBitmap`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TileState {
+    /// Covered by a piece
+    Interior,
+
+    /// Touches an interior tile at a corner, a new piece may be placed here
+    Corner,
+
+    /// Shares an edge with an interior tile, a new piece may never be placed here
+    Side,
+
+    Empty,
+}
+
+impl TileState {
+    /// Get the character used for this state in a bitmap's text format
+    pub const fn as_char(self) -> char {
+        match self {
+            TileState::Interior => 'i',
+            TileState::Corner => 'c',
+            TileState::Side => 's',
+            TileState::Empty => '.',
+        }
+    }
+
+    pub const fn from_char(c: char) -> Option<TileState> {
+        match c {
+            'i' => Some(TileState::Interior),
+            'c' => Some(TileState::Corner),
+            's' => Some(TileState::Side),
+            '.' => Some(TileState::Empty),
+            _ => None,
+        }
+    }
+}
+
+/// A single player Blokus position (or a polyomino) stored as three disjoint planes: side, corner and interior.
+///
+/// This is the model used by the `Blokus` class in `cgt.js`, the text format uses one character per tile
+/// (`i` interior, `c` corner, `s` side and `.` empty) with one row per line.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlokusBitmap<BitsT: BitArray = WordVec> {
+    side: BitBoard<DynGeometry, BitsT>,
+    corner: BitBoard<DynGeometry, BitsT>,
+    interior: BitBoard<DynGeometry, BitsT>,
+}
+
+impl<BitsT: BitArray> BlokusBitmap<BitsT> {
+    pub fn empty(width: i16, height: i16) -> Self {
+        let geometry = DynGeometry::new(width, height);
+        Self {
+            side: BitBoard::new(geometry),
+            corner: BitBoard::new(geometry),
+            interior: BitBoard::new(geometry),
+        }
+    }
+
+    /// Create a polyomino bitmap from a piece's shape.
+    /// The shape is surrounded by a one tile border, so all of its side and corner tiles are included.
+    pub fn from_piece(piece: &BlokusPiece) -> Self {
+        let geometry = DynGeometry::new(piece.shape.geometry().width() + 2, piece.shape.geometry().height() + 2);
+        let mut interior = BitBoard::new(geometry);
+        for coord in piece.shape.iter_set() {
+            interior.set(coord + Coordinates::new(1, 1), true);
+        }
+
+        Self {
+            side: interior.orthogonal_neighbors(),
+            corner: interior.diagonal_neighbors(),
+            interior,
+        }
+    }
+
+    pub fn width(&self) -> i16 {
+        self.interior.geometry().width()
+    }
+
+    pub fn height(&self) -> i16 {
+        self.interior.geometry().height()
+    }
+
+    pub fn side(&self) -> &BitBoard<DynGeometry, BitsT> {
+        &self.side
+    }
+
+    pub fn corner(&self) -> &BitBoard<DynGeometry, BitsT> {
+        &self.corner
+    }
+
+    pub fn interior(&self) -> &BitBoard<DynGeometry, BitsT> {
+        &self.interior
+    }
+
+    pub fn get(&self, coord: Coordinates) -> TileState {
+        if self.side.get(coord) {
+            TileState::Side
+        } else if self.corner.get(coord) {
+            TileState::Corner
+        } else if self.interior.get(coord) {
+            TileState::Interior
+        } else {
+            TileState::Empty
+        }
+    }
+
+    pub fn set(&mut self, coord: Coordinates, state: TileState) {
+        self.side.set(coord, state == TileState::Side);
+        self.corner.set(coord, state == TileState::Corner);
+        self.interior.set(coord, state == TileState::Interior);
+    }
+
+    pub fn count_interior(&self) -> u32 {
+        self.interior.count_ones()
+    }
+
+    /// Returns true if no tile is in more than one plane
+    pub fn is_valid(&self) -> bool {
+        !self.corner.intersects(&self.interior)
+            && !self.corner.intersects(&self.side)
+            && !self.side.intersects(&self.interior)
+    }
+
+    /// Panic if any tile is in more than one plane
+    pub fn assert_valid(&self) {
+        assert!(!self.corner.intersects(&self.interior), "corner and interior tiles overlap");
+        assert!(!self.corner.intersects(&self.side), "corner and side tiles overlap");
+        assert!(!self.side.intersects(&self.interior), "side and interior tiles overlap");
+    }
+
+    /// Copy this bitmap onto one with a different size, tiles stay at the same coordinates
+    pub fn resize(&self, width: i16, height: i16) -> Self {
+        let geometry = DynGeometry::new(width, height);
+        Self {
+            side: self.side.resize(geometry),
+            corner: self.corner.resize(geometry),
+            interior: self.interior.resize(geometry),
+        }
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        Self {
+            side: self.side.rotate_clockwise(),
+            corner: self.corner.rotate_clockwise(),
+            interior: self.interior.rotate_clockwise(),
+        }
+    }
+
+    /// Move every tile by the given offset, tiles moved off the bitmap are dropped
+    pub fn translate_in_place(&mut self, dx: i16, dy: i16) {
+        self.side.shift_in_place(dx, dy);
+        self.corner.shift_in_place(dx, dy);
+        self.interior.shift_in_place(dx, dy);
+    }
+
+    /// Place `polyomino` so its tile at `origin` lands on `at`, a port of `Blokus.tryPlacePolyomino` in `cgt.js`.
+    ///
+    /// Returns false, leaving the bitmap unchanged, if part of the polyomino's interior would be off the bitmap,
+    /// if its interior or sides would cover an existing interior tile, or if the offset `at - origin` doesn't fit in an `i16`.
+    ///
+    /// The result differs from the original where the original loses or misplaces tiles:
+    /// - The original crops the polyomino to this bitmap's size before translating it, so its tiles past this bitmap's
+    ///   width or height are dropped even when the translation would bring them onto the bitmap.
+    ///   Here the polyomino is translated first, so e.g. an upright L4 fits on a 5x3 bitmap, and a flat one keeps its bottom border.
+    /// - The original's `BitBoard.translateInPlace` shifts by the bitmap's height instead of its width for each row moved down,
+    ///   and its `BitBoard.resize` reads rows with the new width when shrinking.
+    ///   Both put tiles in the wrong place when the bitmap isn't square or is narrower than the polyomino,
+    ///   here tiles always move by exactly `at - origin`.
+    pub fn try_place_polyomino(&mut self, at: Coordinates, polyomino: &Self, origin: Coordinates) -> bool {
+        let (Some(dx), Some(dy)) = (at.x.checked_sub(origin.x), at.y.checked_sub(origin.y)) else {
+            return false;
+        };
+
+        // translate before cropping to this bitmap's size, so tiles that start outside of it aren't lost
+        let mut placed = polyomino.resize(
+            self.width().max(polyomino.width()),
+            self.height().max(polyomino.height()),
+        );
+        placed.translate_in_place(dx, dy);
+        let placed = placed.resize(self.width(), self.height());
+        if placed.count_interior() != polyomino.count_interior() {
+            return false;
+        }
+
+        if self.interior.intersects(&(placed.interior.clone() | placed.side.clone())) {
+            return false;
+        }
+
+        self.interior |= placed.interior.clone();
+        self.side |= placed.side.and_not(&self.interior);
+        self.corner |= placed.corner.and_not(&(self.interior.clone() | self.side.clone()));
+        self.corner = self.corner.and_not(&(placed.interior.clone() | placed.side));
+        self.side = self.side.and_not(&placed.interior);
+        true
+    }
+
+    /// Find the positions reachable by placing one of `polyominoes`, in any of its 4 rotations, a port of `Blokus.moves` in `cgt.js`.
+    ///
+    /// For each corner tile of the rotated polyomino, the tile diagonally below and to the right of it is put on each
+    /// corner tile of this bitmap. Like the original, polyominoes are only rotated, not mirrored,
+    /// and the same position may be returned more than once.
+    /// Pieces are placed by [`Self::try_place_polyomino`], so this finds positions the original misses, see its differences.
+    pub fn moves(&self, polyominoes: &[Self]) -> Vec<Self> {
+        let mut moves = Vec::new();
+        for polyomino in polyominoes {
+            let mut rotated = polyomino.clone();
+            for rotation in 0..4 {
+                if rotation > 0 {
+                    rotated = rotated.rotate_clockwise();
+                }
+
+                for at in self.corner.iter_set() {
+                    for corner in rotated.corner.iter_set() {
+                        let mut next = self.clone();
+                        if next.try_place_polyomino(at, &rotated, corner + Coordinates::new(1, 1)) {
+                            moves.push(next);
+                        }
+                    }
+                }
+            }
+        }
+        moves
+    }
+}
+
+impl<BitsT: BitArray + Eq + Hash> BlokusBitmap<BitsT> {
+    /// The combinatorial game value of this position, when players take turns placing any of `polyominoes` and the first player who can't move loses.
+    ///
+    /// This is the game the demo solves with `canonicalForm(moves, moves)`: both players share one position, so they have the same moves,
+    /// and the game is impartial. Every impartial game equals a nimber, this returns `n` for the nimber `*n`,
+    /// so 0 means the player to move loses and anything else means they win.
+    ///
+    /// Moves are found by [`Self::moves`], each position's value is the smallest one none of its moves lead to.
+    /// Positions reached more than once are only evaluated once.
+    pub fn nim_value(&self, polyominoes: &[Self]) -> u32 {
+        self.nim_value_memoized(polyominoes, &mut HashMap::new())
+    }
+
+    fn nim_value_memoized(&self, polyominoes: &[Self], values: &mut HashMap<Self, u32>) -> u32 {
+        if let Some(&value) = values.get(self) {
+            return value;
+        }
+
+        let mut reachable: Vec<u32> = self
+            .moves(polyominoes)
+            .iter()
+            .map(|next| next.nim_value_memoized(polyominoes, values))
+            .collect();
+        reachable.sort_unstable();
+        reachable.dedup();
+        let value = (0..).zip(&reachable).find(|&(n, &value)| n != value).map_or(reachable.len() as u32, |(n, _)| n);
+
+        values.insert(self.clone(), value);
+        value
+    }
+}
+
+/// An error encountered while reading a [`BlokusBitmap`] from text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseBlokusBitmapError {
+    /// zero-based line number, counting only non-empty lines
+    pub line: usize,
+
+    /// zero-based column number, ignoring leading whitespace
+    pub column: usize,
+
+    pub character: char,
+}
+
+impl fmt::Display for ParseBlokusBitmapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unexpected character {:?} at line {}, column {}",
+            self.character, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for ParseBlokusBitmapError {}
+
+impl<BitsT: BitArray> FromStr for BlokusBitmap<BitsT> {
+    type Err = ParseBlokusBitmapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<&str> = s.lines().map(str::trim).filter(|r| !r.is_empty()).collect();
+        let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+
+        let mut bitmap = Self::empty(width as i16, rows.len() as i16);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, character) in row.chars().enumerate() {
+                let state = TileState::from_char(character).ok_or(ParseBlokusBitmapError {
+                    line: y,
+                    column: x,
+                    character,
+                })?;
+                bitmap.set(Coordinates::new(x as i16, y as i16), state);
+            }
+        }
+
+        Ok(bitmap)
+    }
+}
+
+impl<BitsT: BitArray> fmt::Display for BlokusBitmap<BitsT> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                write!(f, "{}", self.get(Coordinates::new(x, y)).as_char())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BlokusBitmap, TileState};
+    use crate::{bitboard::Coordinates, ruleset::BlokusPiece};
+
+    const L4: &str = r#"csc
+                        sis
+                        sisc
+                        siis
+                        cssc"#;
+
+    #[test]
+    fn l4_piece_matches_demo_polyomino() {
+        let from_piece: BlokusBitmap = BlokusBitmap::from_piece(&BlokusPiece::parse("x .|x .|x x"));
+        let from_str: BlokusBitmap = L4.parse().unwrap();
+        assert_eq!(from_piece, from_str);
+        assert_eq!(from_piece.to_string(), "csc.\nsis.\nsisc\nsiis\ncssc\n");
+    }
+
+    #[test]
+    fn l4_moves_on_3x5_board() {
+        let l4: BlokusBitmap = L4.parse().unwrap();
+        let mut board = BlokusBitmap::empty(5, 3);
+        board.set(Coordinates::zero(), TileState::Corner);
+
+        // standing upright, standing upside down, and lying flat, mirrored placements need a mirrored polyomino.
+        // cgt.js only finds the flat one, since it crops the polyomino to the board before moving it
+        let mut moves = board.moves(&[l4]);
+        moves.sort_by_key(|mv| mv.to_string());
+        moves.dedup();
+        assert_eq!(moves.len(), 3);
+        assert!(moves.iter().all(|mv| mv.get(Coordinates::zero()) == TileState::Interior));
+    }
+
+    #[test]
+    fn reject_unexpected_character() {
+        let err = "c.\n.x".parse::<BlokusBitmap>().unwrap_err();
+        assert_eq!((err.line, err.column, err.character), (1, 1, 'x'));
+    }
+
+    #[test]
+    fn place_l4_on_corner_of_empty_board() {
+        let l4: BlokusBitmap = L4.parse().unwrap();
+        let mut board = BlokusBitmap::empty(5, 3);
+        board.set(Coordinates::zero(), TileState::Corner);
+
+        // rotate so the L lies flat, then put its top-left interior tile in the corner
+        let flat = l4.rotate_clockwise();
+        assert!(board.try_place_polyomino(Coordinates::zero(), &flat, Coordinates::new(1, 1)));
+        board.assert_valid();
+        assert_eq!(board.to_string(), "iiis.\nissc.\nsc...\n");
+
+        // overlaps the piece that was just placed
+        let before = board.clone();
+        assert!(!board.try_place_polyomino(Coordinates::new(1, 1), &flat, Coordinates::new(1, 1)));
+        assert_eq!(board, before);
+
+        // falls off the right side of the board
+        assert!(!board.try_place_polyomino(Coordinates::new(4, 0), &flat, Coordinates::new(1, 1)));
+
+        // the offset from the polyomino's origin doesn't fit in an i16, or moves it far off the board
+        assert!(!board.try_place_polyomino(Coordinates::new(i16::MIN, 0), &flat, Coordinates::new(1, 1)));
+        assert!(!board.try_place_polyomino(Coordinates::new(i16::MAX, i16::MAX), &flat, Coordinates::zero()));
+        assert!(!board.try_place_polyomino(Coordinates::new(-i16::MAX, 0), &flat, Coordinates::zero()));
+        assert_eq!(board, before);
+    }
+
+    #[test]
+    fn place_polyomino_where_cgt_js_loses_tiles() {
+        // cgt.js crops the 5 row polyomino to 3 rows before moving it up, losing an interior tile, and returns false
+        let l4: BlokusBitmap = L4.parse().unwrap();
+        let mut board = BlokusBitmap::empty(5, 3);
+        assert!(board.try_place_polyomino(Coordinates::zero(), &l4, Coordinates::new(1, 1)));
+        assert_eq!(board.to_string(), "is...\nisc..\niis..\n");
+
+        // cgt.js drops the bottom border, giving "iiis.\nissc.\n.....\n"
+        let mut board = BlokusBitmap::empty(5, 3);
+        assert!(board.try_place_polyomino(Coordinates::zero(), &l4.rotate_clockwise(), Coordinates::new(1, 1)));
+        assert_eq!(board.to_string(), "iiis.\nissc.\nsc...\n");
+    }
+
+    #[test]
+    fn place_polyomino_where_cgt_js_misplaces_tiles() {
+        let monomino: BlokusBitmap = "csc\nsis\ncsc".parse().unwrap();
+
+        // moving down on a bitmap that isn't square, cgt.js gives "...csc\n...sis\n......\n"
+        let mut board = BlokusBitmap::empty(6, 3);
+        assert!(board.try_place_polyomino(Coordinates::new(1, 2), &monomino, Coordinates::new(1, 1)));
+        assert_eq!(board.to_string(), "......\ncsc...\nsis...\n");
+
+        // on a bitmap narrower than the polyomino, cgt.js gives "cs\ncs\nis\n"
+        let mut board = BlokusBitmap::empty(2, 3);
+        assert!(board.try_place_polyomino(Coordinates::new(1, 1), &monomino, Coordinates::new(1, 1)));
+        assert_eq!(board.to_string(), "cs\nsi\ncs\n");
+    }
+
+    #[test]
+    fn nim_value_of_small_games() {
+        let monomino: BlokusBitmap = "csc\nsis\ncsc".parse().unwrap();
+        let domino: BlokusBitmap = "cssc\nsiis\ncssc".parse().unwrap();
+
+        // nothing to place, the player to move loses
+        let mut board = BlokusBitmap::empty(1, 1);
+        board.set(Coordinates::zero(), TileState::Corner);
+        assert_eq!(board.nim_value(&[]), 0);
+
+        // a single move, then the next player is stuck: *
+        assert_eq!(board.nim_value(std::slice::from_ref(&monomino)), 1);
+
+        // the first monomino leaves a single corner for the second, so the first player loses
+        let mut board = BlokusBitmap::empty(2, 2);
+        board.set(Coordinates::zero(), TileState::Corner);
+        assert_eq!(board.nim_value(std::slice::from_ref(&monomino)), 0);
+
+        // a domino fills the board's first row or column and ends the game, a monomino still moves to *, so this is *2
+        assert_eq!(board.nim_value(&[monomino, domino]), 2);
+    }
+}
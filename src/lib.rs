@@ -1,7 +1,14 @@
-#![feature(const_trait_impl)]
-#![feature(const_ops)]
-#![feature(generic_const_exprs)]
-#![feature(generic_const_items)]
+#![cfg_attr(feature = "nightly", feature(const_trait_impl))]
+#![cfg_attr(feature = "nightly", feature(const_ops))]
+#![cfg_attr(feature = "nightly", feature(generic_const_exprs))]
+#![cfg_attr(feature = "nightly", feature(generic_const_items))]
+#![cfg_attr(feature = "nightly", allow(incomplete_features))]
+
+#[macro_use]
+mod macros;
 
 pub mod bitboard;
-pub mod ruleset;
\ No newline at end of file
+pub mod bitmap;
+pub mod ruleset;
+#[cfg(feature = "wasm")]
+pub mod wasm;
\ No newline at end of file
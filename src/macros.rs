@@ -0,0 +1,100 @@
+//! Helpers for items that can only be `const` on nightly.
+//!
+//! With the `nightly` feature enabled these expand to `const fn`, `impl const` and `const trait` items,
+//! otherwise the same items are emitted without `const` so the crate builds on stable Rust.
+//! Stable rejects the const trait syntax even in code that is disabled with `#[cfg]`,
+//! so only the matching version of each macro is ever defined.
+
+/// Define functions in an inherent `impl` block that are `const` on nightly.
+/// Any `[const]` bounds in the where clause are dropped on stable.
+#[cfg(feature = "nightly")]
+macro_rules! const_fn {
+    ($(
+        $(#[$attr:meta])*
+        $vis:vis fn $name:ident($($params:tt)*) $(-> $ret:ty)?
+        $(where $($bounded:ident: [const] $bound:ident),+ $(,)?)?
+        $body:block
+    )*) => {$(
+        $(#[$attr])*
+        $vis const fn $name($($params)*) $(-> $ret)?
+        $(where $($bounded: [const] $bound),+)?
+        $body
+    )*};
+}
+
+#[cfg(not(feature = "nightly"))]
+macro_rules! const_fn {
+    ($(
+        $(#[$attr:meta])*
+        $vis:vis fn $name:ident($($params:tt)*) $(-> $ret:ty)?
+        $(where $($bounded:ident: [const] $bound:ident),+ $(,)?)?
+        $body:block
+    )*) => {$(
+        $(#[$attr])*
+        $vis fn $name($($params)*) $(-> $ret)?
+        $body
+    )*};
+}
+
+/// Implement a trait that is `const` on nightly, i.e. `impl const Trait for Type` without the `const`.
+#[cfg(feature = "nightly")]
+macro_rules! const_impl {
+    (
+        $(#[$attr:meta])*
+        impl $(<$($lifetime:lifetime),* $(,)? $(const $param:ident: $param_ty:ty),*>)?
+            $($trait:ident)::+ $(<$($trait_arg:ty),+>)? for $ty:ty { $($body:tt)* }
+    ) => {
+        $(#[$attr])*
+        impl $(<$($lifetime,)* $(const $param: $param_ty),*>)?
+            const $($trait)::+ $(<$($trait_arg),+>)? for $ty { $($body)* }
+    };
+}
+
+#[cfg(not(feature = "nightly"))]
+macro_rules! const_impl {
+    (
+        $(#[$attr:meta])*
+        impl $(<$($lifetime:lifetime),* $(,)? $(const $param:ident: $param_ty:ty),*>)?
+            $($trait:ident)::+ $(<$($trait_arg:ty),+>)? for $ty:ty { $($body:tt)* }
+    ) => {
+        $(#[$attr])*
+        impl $(<$($lifetime,)* $(const $param: $param_ty),*>)?
+            $($trait)::+ $(<$($trait_arg),+>)? for $ty { $($body)* }
+    };
+}
+
+/// Define a trait that can be implemented with `const_impl!` on nightly.
+/// Supertraits marked `[const]` must be implemented as const on nightly, and are plain supertraits on stable.
+#[cfg(feature = "nightly")]
+macro_rules! const_trait {
+    (
+        $(#[$attr:meta])*
+        $vis:vis trait $name:ident:
+            $([const] $const_bound:ident $(<$($bound_arg:ident $(= $bound_ty:ty)?),+>)? +)*
+            $bound:ident $(+ $more_bounds:ident)*
+        { $($body:tt)* }
+    ) => {
+        $(#[$attr])*
+        $vis const trait $name:
+            $([const] $const_bound $(<$($bound_arg $(= $bound_ty)?),+>)? +)*
+            $bound $(+ $more_bounds)*
+        { $($body)* }
+    };
+}
+
+#[cfg(not(feature = "nightly"))]
+macro_rules! const_trait {
+    (
+        $(#[$attr:meta])*
+        $vis:vis trait $name:ident:
+            $([const] $const_bound:ident $(<$($bound_arg:ident $(= $bound_ty:ty)?),+>)? +)*
+            $bound:ident $(+ $more_bounds:ident)*
+        { $($body:tt)* }
+    ) => {
+        $(#[$attr])*
+        $vis trait $name:
+            $($const_bound $(<$($bound_arg $(= $bound_ty)?),+>)? +)*
+            $bound $(+ $more_bounds)*
+        { $($body)* }
+    };
+}
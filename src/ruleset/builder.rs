@@ -0,0 +1,302 @@
+use std::fmt;
+
+use crate::bitboard::{BitArray, BitBoard, Coordinates, DynGeometry};
+
+use super::{BlokusPieceSet, BlokusPieceTable, BlokusRuleset, ColorOwner, StandardBlokusPieceSet};
+
+/// Where a colour's first piece may be placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StartTiles {
+    /// the first piece must cover this tile
+    Tile(Coordinates),
+
+    /// the first piece must cover one of the board's four corners
+    AnyCorner,
+}
+
+/// Build a [`BlokusRuleset`] with a custom board size, number of players, start tiles and piece set
+///
+/// By default there are 4 players with one colour each, starting in the top-left, top-right, bottom-right and bottom-left corners,
+/// using the standard pieces.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlokusRulesetBuilder {
+    width: i16,
+    height: i16,
+    player_count: usize,
+    colors_per_player: usize,
+    shared_colors: usize,
+    start_tiles: Option<Vec<StartTiles>>,
+    pieces: BlokusPieceTable,
+}
+
+/// The reason a [`BlokusRulesetBuilder`] could not build a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuildRulesetError {
+    /// the board has no tiles
+    EmptyBoard,
+
+    /// the board's width or height is negative
+    InvalidSize { width: i16, height: i16 },
+
+    /// the board has more tiles than the bit array used to store it can hold
+    TooLarge { tiles: usize, max: usize },
+
+    /// there must be at least one player, and each player must have at least one colour
+    NoColors,
+
+    /// the number of colours is too large to count
+    TooManyColors,
+
+    /// start tiles must be given for each colour
+    WrongStartCount { expected: usize, found: usize },
+
+    /// a start tile is off the board
+    StartOutOfBounds(Coordinates),
+
+    /// piece sets are limited to 64 pieces
+    TooManyPieces(usize),
+}
+
+impl fmt::Display for BuildRulesetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildRulesetError::EmptyBoard => write!(f, "the board has no tiles"),
+            BuildRulesetError::InvalidSize { width, height } => write!(f, "invalid board size {}x{}", width, height),
+            BuildRulesetError::TooLarge { tiles, max } => {
+                write!(f, "the board has {} tiles, but can store at most {}", tiles, max)
+            }
+            BuildRulesetError::NoColors => write!(f, "the game has no colours"),
+            BuildRulesetError::TooManyColors => write!(f, "the game has too many colours"),
+            BuildRulesetError::WrongStartCount { expected, found } => {
+                write!(f, "expected start tiles for {} colours, found {}", expected, found)
+            }
+            BuildRulesetError::StartOutOfBounds(at) => write!(f, "start tile ({}, {}) is off the board", at.x, at.y),
+            BuildRulesetError::TooManyPieces(count) => write!(f, "{} pieces is more than the limit of 64", count),
+        }
+    }
+}
+
+impl std::error::Error for BuildRulesetError {}
+
+impl BlokusRulesetBuilder {
+    pub fn new(width: i16, height: i16) -> Self {
+        Self {
+            width,
+            height,
+            player_count: 4,
+            colors_per_player: 1,
+            shared_colors: 0,
+            start_tiles: None,
+            pieces: StandardBlokusPieceSet::table(),
+        }
+    }
+
+    pub fn players(mut self, player_count: usize) -> Self {
+        self.player_count = player_count;
+        self
+    }
+
+    /// Give each player several colours, players take turns in order for each colour, so with 2 players and 2 colours each
+    /// player 0 has colours 0 and 2, and player 1 has colours 1 and 3.
+    pub fn colors_per_player(mut self, colors_per_player: usize) -> Self {
+        self.colors_per_player = colors_per_player;
+        self
+    }
+
+    /// Add colours played by each player in turn, after the players' own colours, see [`ColorOwner::Shared`].
+    /// The official three player game has one shared colour.
+    pub fn shared_colors(mut self, shared_colors: usize) -> Self {
+        self.shared_colors = shared_colors;
+        self
+    }
+
+    /// Set the start tiles of each colour.
+    /// If this isn't called each colour starts in its own corner, or in any corner if there are more than 4 colours.
+    pub fn start_tiles(mut self, start_tiles: Vec<StartTiles>) -> Self {
+        self.start_tiles = Some(start_tiles);
+        self
+    }
+
+    pub fn pieces(mut self, pieces: BlokusPieceTable) -> Self {
+        self.pieces = pieces;
+        self
+    }
+
+    /// The number of colours the game will have, or `None` if there are too many to count
+    pub fn color_count(&self) -> Option<usize> {
+        self.owned_color_count()?.checked_add(self.shared_colors)
+    }
+
+    fn owned_color_count(&self) -> Option<usize> {
+        self.player_count.checked_mul(self.colors_per_player)
+    }
+
+    pub fn build<BitsT: BitArray>(&self) -> Result<BlokusRuleset<DynGeometry, BitsT>, BuildRulesetError> {
+        let (width, height) = (self.width, self.height);
+        if width < 0 || height < 0 {
+            return Err(BuildRulesetError::InvalidSize { width, height });
+        }
+        if width == 0 || height == 0 {
+            return Err(BuildRulesetError::EmptyBoard);
+        }
+
+        let tiles = width as usize * height as usize;
+        if tiles > BitsT::MAX_LENGTH {
+            return Err(BuildRulesetError::TooLarge { tiles, max: BitsT::MAX_LENGTH });
+        }
+        let geometry = DynGeometry::new(width, height);
+
+        if self.player_count == 0 || self.colors_per_player == 0 {
+            return Err(BuildRulesetError::NoColors);
+        }
+        let (Some(color_count), Some(owned_colors)) = (self.color_count(), self.owned_color_count()) else {
+            return Err(BuildRulesetError::TooManyColors);
+        };
+
+        if self.pieces.pieces.len() > u64::BITS as usize {
+            return Err(BuildRulesetError::TooManyPieces(self.pieces.pieces.len()));
+        }
+
+        let corners = [
+            Coordinates::new(0, 0),
+            Coordinates::new(width - 1, 0),
+            Coordinates::new(width - 1, height - 1),
+            Coordinates::new(0, height - 1),
+        ];
+        let start_tiles = match &self.start_tiles {
+            Some(start_tiles) if start_tiles.len() != color_count => {
+                return Err(BuildRulesetError::WrongStartCount {
+                    expected: color_count,
+                    found: start_tiles.len(),
+                })
+            }
+            Some(start_tiles) => start_tiles.clone(),
+            None if color_count <= corners.len() => corners[..color_count].iter().copied().map(StartTiles::Tile).collect(),
+            None => vec![StartTiles::AnyCorner; color_count],
+        };
+
+        let mut starts = Vec::with_capacity(color_count);
+        for start_tile in start_tiles {
+            let mut start = BitBoard::new(geometry);
+            match start_tile {
+                StartTiles::Tile(tile) if !start.is_coordinate_in_bounds(tile) => {
+                    return Err(BuildRulesetError::StartOutOfBounds(tile))
+                }
+                StartTiles::Tile(tile) => start.set(tile, true),
+                StartTiles::AnyCorner => {
+                    for corner in corners {
+                        start.set(corner, true);
+                    }
+                }
+            }
+            starts.push(start);
+        }
+
+        let mut game = BlokusRuleset::with_start_masks(geometry, self.pieces, starts);
+        let owners = (0..color_count)
+            .map(|color| match color < owned_colors {
+                true => ColorOwner::Player(color % self.player_count),
+                false => ColorOwner::Shared,
+            })
+            .collect();
+        game.set_owners(self.player_count, owners);
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BlokusRulesetBuilder, BuildRulesetError, StartTiles};
+    use crate::{
+        bitboard::{BitBoard, Coordinates, DynGeometry, WordVec},
+        ruleset::{BlokusPieceSet, BlokusRuleset, ColorOwner, LTrominoPieceSet, Move},
+    };
+
+    #[test]
+    fn small_board_with_few_pieces() {
+        let game: BlokusRuleset<DynGeometry, u64> = BlokusRulesetBuilder::new(6, 6)
+            .players(2)
+            .pieces(LTrominoPieceSet::table())
+            .build()
+            .unwrap();
+        assert_eq!(game.color_count(), 2);
+        assert_eq!(game.player_count(), 2);
+        assert!(game.color(1).start().get(Coordinates::new(5, 0)));
+
+        // 3 of the L tromino's 4 orientations fit in the corner
+        assert_eq!(game.legal_moves().count(), 3);
+    }
+
+    #[test]
+    fn players_with_several_colours() {
+        let mut game: BlokusRuleset<DynGeometry, WordVec> =
+            BlokusRulesetBuilder::new(20, 20).players(2).colors_per_player(2).build().unwrap();
+        assert_eq!(game.color_count(), 4);
+        assert_eq!(game.color_owner(2), ColorOwner::Player(0));
+        assert_eq!(game.color_owner(3), ColorOwner::Player(1));
+
+        game.apply(Move::Pass);
+        assert_eq!(game.current_color(), 1);
+        assert_eq!(game.current_player(), 1);
+        game.apply(Move::Pass);
+        assert_eq!(game.current_player(), 0);
+    }
+
+    #[test]
+    fn three_players_with_a_shared_colour() {
+        let game: BlokusRuleset<DynGeometry, WordVec> =
+            BlokusRulesetBuilder::new(20, 20).players(3).shared_colors(1).build().unwrap();
+        assert_eq!(game.color_count(), 4);
+        assert_eq!(game.color_owner(2), ColorOwner::Player(2));
+        assert_eq!(game.color_owner(3), ColorOwner::Shared);
+        assert!(game.color(3).start().get(Coordinates::new(0, 19)));
+    }
+
+    #[test]
+    fn any_corner_start() {
+        let game: BlokusRuleset<DynGeometry, u64> = BlokusRulesetBuilder::new(7, 5)
+            .players(1)
+            .start_tiles(vec![StartTiles::AnyCorner])
+            .build()
+            .unwrap();
+
+        let mut corners = BitBoard::new(DynGeometry::new(7, 5));
+        for corner in [(0, 0), (6, 0), (6, 4), (0, 4)] {
+            corners.set(Coordinates::new(corner.0, corner.1), true);
+        }
+        assert_eq!(game.color(0).start(), &corners);
+    }
+
+    #[test]
+    fn invalid_configurations() {
+        let builder = BlokusRulesetBuilder::new(6, 6).players(2);
+        assert_eq!(
+            builder.clone().start_tiles(vec![StartTiles::AnyCorner]).build::<u64>(),
+            Err(BuildRulesetError::WrongStartCount { expected: 2, found: 1 })
+        );
+        assert_eq!(
+            builder
+                .clone()
+                .start_tiles(vec![StartTiles::AnyCorner, StartTiles::Tile(Coordinates::new(6, 0))])
+                .build::<u64>(),
+            Err(BuildRulesetError::StartOutOfBounds(Coordinates::new(6, 0)))
+        );
+        assert_eq!(builder.clone().players(0).build::<u64>(), Err(BuildRulesetError::NoColors));
+        assert_eq!(
+            builder.clone().colors_per_player(0).shared_colors(1).build::<u64>(),
+            Err(BuildRulesetError::NoColors)
+        );
+        assert_eq!(builder.clone().colors_per_player(usize::MAX).build::<u64>(), Err(BuildRulesetError::TooManyColors));
+        assert_eq!(builder.shared_colors(usize::MAX).build::<u64>(), Err(BuildRulesetError::TooManyColors));
+        assert_eq!(BlokusRulesetBuilder::new(0, 6).build::<u64>(), Err(BuildRulesetError::EmptyBoard));
+        assert_eq!(
+            BlokusRulesetBuilder::new(-1, 5).build::<u64>(),
+            Err(BuildRulesetError::InvalidSize { width: -1, height: 5 })
+        );
+        assert_eq!(
+            BlokusRulesetBuilder::new(20, 20).build::<u64>(),
+            Err(BuildRulesetError::TooLarge { tiles: 400, max: 64 })
+        );
+        assert!(BlokusRulesetBuilder::new(8, 8).build::<u64>().is_ok());
+    }
+}
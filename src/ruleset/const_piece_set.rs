@@ -0,0 +1,178 @@
+use super::{BlokusPiece, BlokusPieceTable};
+use crate::bitboard::{BitBoard, Coordinates, DynGeometry};
+
+/// A set of pieces, with every orientation and corner cell computed at compile time.
+///
+/// Each table is also available from a function, like [`BlokusPieceSet::orientations`] for `ORIENTATIONS`,
+/// which is all the crate provides without the `nightly` feature.
+pub trait BlokusPieceSet<const N: usize> {
+    const PIECES: [BlokusPiece; N];
+
+    /// The total number of distinct orientations of all pieces in the set
+    const ORIENTATION_COUNT: usize = {
+        let mut orientation_count: usize = 0;
+        let mut i = 0;
+        while i < N {
+            orientation_count += Self::PIECES[i].count_orientations();
+            i += 1;
+        }
+
+        orientation_count
+    };
+
+    /// The index into `ORIENTATIONS` of each piece's first orientation
+    const ORIENTATION_OFFSETS: [usize; N] = {
+        let mut current_orientation_count: usize = 0;
+        let mut orientation_offsets: [usize; N] = [0; N];
+        let mut i = 0;
+        while i < N {
+            orientation_offsets[i] = current_orientation_count;
+            current_orientation_count += Self::PIECES[i].count_orientations();
+            i += 1;
+        }
+
+        orientation_offsets
+    };
+
+    /// Every distinct orientation of every piece, grouped by piece in the same order as `PIECES`
+    const ORIENTATIONS: [BlokusPiece; Self::ORIENTATION_COUNT] = {
+        let mut orientations = [BlokusPiece {
+            shape: BitBoard::new_with_data(DynGeometry::new(0, 0), 0),
+        }; Self::ORIENTATION_COUNT];
+        let mut piece_i = 0;
+        while piece_i < N {
+            let orientation_count = Self::PIECES[piece_i].count_orientations();
+            let mut local_orientation_i = 0;
+            while local_orientation_i < orientation_count {
+                orientations[Self::ORIENTATION_OFFSETS[piece_i] + local_orientation_i] =
+                    Self::PIECES[piece_i].nth_orientation(local_orientation_i);
+                local_orientation_i += 1;
+            }
+            piece_i += 1;
+        }
+
+        orientations
+    } where [(); Self::ORIENTATION_COUNT]:;
+
+    /// The total number of corner cells (see [`BlokusPiece::count_corner_cells`]) in every orientation of every piece
+    const CORNER_COUNT: usize = {
+        let mut corner_count: usize = 0;
+        let mut piece_i = 0;
+        while piece_i < N {
+            let orientation_count = Self::PIECES[piece_i].count_orientations();
+            let mut local_orientation_i = 0;
+            while local_orientation_i < orientation_count {
+                corner_count += Self::PIECES[piece_i].nth_orientation(local_orientation_i).count_corner_cells();
+                local_orientation_i += 1;
+            }
+            piece_i += 1;
+        }
+
+        corner_count
+    };
+
+    /// The index into `CORNERS` of each orientation's first corner cell, indexed the same way as `ORIENTATIONS`
+    const CORNER_OFFSETS: [usize; Self::ORIENTATION_COUNT] = {
+        let mut current_corner_count: usize = 0;
+        let mut corner_offsets = [0; Self::ORIENTATION_COUNT];
+        let mut i = 0;
+        while i < Self::ORIENTATION_COUNT {
+            corner_offsets[i] = current_corner_count;
+            current_corner_count += Self::ORIENTATIONS[i].count_corner_cells();
+            i += 1;
+        }
+
+        corner_offsets
+    } where [(); Self::ORIENTATION_COUNT]:;
+
+    /// The corner cells of every orientation of every piece, relative to the orientation's top-left corner.
+    /// A new piece can only touch its own colour through one of these cells.
+    const CORNERS: [Coordinates; Self::CORNER_COUNT] = {
+        let mut corner_coordinates = [Coordinates::zero(); Self::CORNER_COUNT];
+        let mut orientation_i = 0;
+        while orientation_i < Self::ORIENTATION_COUNT {
+            let corner_count = Self::ORIENTATIONS[orientation_i].count_corner_cells();
+            let mut local_corner_i = 0;
+            while local_corner_i < corner_count {
+                corner_coordinates[Self::CORNER_OFFSETS[orientation_i] + local_corner_i] =
+                    Self::ORIENTATIONS[orientation_i].nth_corner_cell(local_corner_i);
+                local_corner_i += 1;
+            }
+            orientation_i += 1;
+        }
+
+        corner_coordinates
+    } where [(); Self::ORIENTATION_COUNT]:, [(); Self::CORNER_COUNT]:;
+
+    /// This set's pieces, orientations and corners, in a form that can be stored in a game
+    const TABLE: BlokusPieceTable = BlokusPieceTable {
+        pieces: &Self::PIECES,
+        orientation_offsets: &Self::ORIENTATION_OFFSETS,
+        orientations: &Self::ORIENTATIONS,
+        corner_offsets: &Self::CORNER_OFFSETS,
+        corners: &Self::CORNERS,
+    } where [(); Self::ORIENTATION_COUNT]:, [(); Self::CORNER_COUNT]:;
+
+    /// Get [`BlokusPieceSet::TABLE`]
+    fn table() -> BlokusPieceTable
+    where
+        [(); Self::ORIENTATION_COUNT]:,
+        [(); Self::CORNER_COUNT]:,
+    {
+        Self::TABLE
+    }
+
+    /// Get [`BlokusPieceSet::ORIENTATION_COUNT`]
+    fn orientation_count() -> usize
+    where
+        [(); Self::ORIENTATION_COUNT]:,
+        [(); Self::CORNER_COUNT]:,
+    {
+        Self::table().orientations.len()
+    }
+
+    /// Get [`BlokusPieceSet::ORIENTATION_OFFSETS`]
+    fn orientation_offsets() -> &'static [usize]
+    where
+        [(); Self::ORIENTATION_COUNT]:,
+        [(); Self::CORNER_COUNT]:,
+    {
+        Self::table().orientation_offsets
+    }
+
+    /// Get [`BlokusPieceSet::ORIENTATIONS`]
+    fn orientations() -> &'static [BlokusPiece]
+    where
+        [(); Self::ORIENTATION_COUNT]:,
+        [(); Self::CORNER_COUNT]:,
+    {
+        Self::table().orientations
+    }
+
+    /// Get [`BlokusPieceSet::CORNER_COUNT`]
+    fn corner_count() -> usize
+    where
+        [(); Self::ORIENTATION_COUNT]:,
+        [(); Self::CORNER_COUNT]:,
+    {
+        Self::table().corners.len()
+    }
+
+    /// Get [`BlokusPieceSet::CORNER_OFFSETS`]
+    fn corner_offsets() -> &'static [usize]
+    where
+        [(); Self::ORIENTATION_COUNT]:,
+        [(); Self::CORNER_COUNT]:,
+    {
+        Self::table().corner_offsets
+    }
+
+    /// Get [`BlokusPieceSet::CORNERS`]
+    fn corners() -> &'static [Coordinates]
+    where
+        [(); Self::ORIENTATION_COUNT]:,
+        [(); Self::CORNER_COUNT]:,
+    {
+        Self::table().corners
+    }
+}
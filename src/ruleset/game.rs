@@ -0,0 +1,683 @@
+use crate::bitboard::{word_count, BitArray, BitBoard, BoardGeometry, Coordinates, StaticGeometry, WordArray};
+
+use super::{BlokusPieceSet, BlokusPieceTable, LegalMoves, Move, MoveError, StandardBlokusPieceSet};
+
+/// The classic four colour game on a 20x20 board
+pub type StandardBlokus = BlokusRuleset<StaticGeometry<20, 20>, WordArray<{ word_count(400) }>>;
+
+/// The two colour game on a 14x14 board, sold as Blokus Duo and Blokus Travel
+pub type DuoBlokus = BlokusRuleset<StaticGeometry<14, 14>, WordArray<{ word_count(196) }>>;
+
+/// The pieces one colour has placed, and the ones it has left
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ColorState<GeomT: BoardGeometry, BitsT: BitArray> {
+    occupied: BitBoard<GeomT, BitsT>,
+    start: BitBoard<GeomT, BitsT>,
+    remaining_pieces: u64,
+    final_piece: Option<u8>,
+}
+
+impl<GeomT: BoardGeometry, BitsT: BitArray> ColorState<GeomT, BitsT> {
+    /// Every tile covered by this colour
+    pub fn occupied(&self) -> &BitBoard<GeomT, BitsT> {
+        &self.occupied
+    }
+
+    /// The tiles this colour's first piece may cover, at least one of them must be covered
+    pub fn start(&self) -> &BitBoard<GeomT, BitsT> {
+        &self.start
+    }
+
+    /// A bit set of the indexes of pieces this colour has yet to place
+    pub fn remaining_pieces(&self) -> u64 {
+        self.remaining_pieces
+    }
+
+    /// The piece this colour placed last, if it has placed all of its pieces
+    pub fn final_piece(&self) -> Option<usize> {
+        self.final_piece.map(usize::from)
+    }
+
+    pub fn has_piece(&self, piece: usize) -> bool {
+        self.remaining_pieces & (1 << piece) != 0
+    }
+
+    /// Returns true if this colour hasn't placed a piece yet
+    pub fn is_first_placement(&self) -> bool {
+        self.occupied.is_empty()
+    }
+
+    /// Tiles this colour may never cover, because they share an edge with one of its pieces
+    pub fn forbidden(&self) -> BitBoard<GeomT, BitsT> {
+        self.occupied.orthogonal_neighbors()
+    }
+
+    /// Tiles where this colour's next piece may be placed, either its start tiles or those touching its pieces at a corner
+    pub fn anchors(&self) -> BitBoard<GeomT, BitsT> {
+        if self.is_first_placement() {
+            self.start.clone()
+        } else {
+            self.occupied.diagonal_neighbors()
+        }
+    }
+}
+
+/// Who plays a colour, and whose score its pieces count towards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorOwner {
+    /// the colour is played by one player, and counts towards their score
+    Player(usize),
+
+    /// the colour is played by each player in turn, and isn't scored
+    Shared,
+}
+
+/// The state of a Blokus game: each colour's pieces on the board, the pieces they have left, and whose turn it is.
+///
+/// Colours take turns in order, starting with colour 0.
+/// A player may control several colours, and a colour may be shared, with players taking turns to play it (see [`ColorOwner`]).
+/// Each new piece must touch a piece of the same colour at a corner, and may never share an edge with one.
+/// A colour's first piece must instead cover one of its start tiles.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlokusRuleset<GeomT: BoardGeometry, BitsT: BitArray> {
+    geometry: GeomT,
+    pieces: BlokusPieceTable,
+    colors: Vec<ColorState<GeomT, BitsT>>,
+    owners: Vec<ColorOwner>,
+    player_count: usize,
+    current_color: usize,
+    turn: usize,
+}
+
+impl StandardBlokus {
+    /// Create a new game with the standard pieces, with colours starting in the top-left, top-right, bottom-right and bottom-left corners
+    pub fn standard() -> Self {
+        Self::with_start_tiles(
+            StaticGeometry::<20, 20>,
+            StandardBlokusPieceSet::table(),
+            &[
+                Coordinates::new(0, 0),
+                Coordinates::new(19, 0),
+                Coordinates::new(19, 19),
+                Coordinates::new(0, 19),
+            ],
+        )
+    }
+
+    /// Create a new three player game, where the fourth colour is shared, played by each player in turn and not scored
+    pub fn three_player() -> Self {
+        let mut game = Self::standard();
+        game.set_owners(
+            3,
+            vec![
+                ColorOwner::Player(0),
+                ColorOwner::Player(1),
+                ColorOwner::Player(2),
+                ColorOwner::Shared,
+            ],
+        );
+        game
+    }
+
+    /// Create a new two player game, where the first player controls colours 0 and 2, and the second controls colours 1 and 3
+    pub fn two_player() -> Self {
+        let mut game = Self::standard();
+        game.set_owners(
+            2,
+            vec![
+                ColorOwner::Player(0),
+                ColorOwner::Player(1),
+                ColorOwner::Player(0),
+                ColorOwner::Player(1),
+            ],
+        );
+        game
+    }
+}
+
+impl DuoBlokus {
+    /// Create a new game with the standard pieces, with colours starting near the centre at (4, 4) and (9, 9)
+    pub fn duo() -> Self {
+        Self::with_start_tiles(
+            StaticGeometry::<14, 14>,
+            StandardBlokusPieceSet::table(),
+            &[Coordinates::new(4, 4), Coordinates::new(9, 9)],
+        )
+    }
+}
+
+impl<GeomT: BoardGeometry, BitsT: BitArray> BlokusRuleset<GeomT, BitsT> {
+    /// Create a new game with one colour for each start tile
+    pub fn with_start_tiles(geometry: GeomT, pieces: BlokusPieceTable, start_tiles: &[Coordinates]) -> Self {
+        let starts = start_tiles
+            .iter()
+            .map(|&tile| {
+                let mut start = BitBoard::new(geometry);
+                start.set(tile, true);
+                start
+            })
+            .collect();
+        Self::with_start_masks(geometry, pieces, starts)
+    }
+
+    /// Create a new game with one colour for each start mask, a colour's first piece must cover at least one tile in its mask.
+    /// Each colour belongs to a different player.
+    pub fn with_start_masks(geometry: GeomT, pieces: BlokusPieceTable, starts: Vec<BitBoard<GeomT, BitsT>>) -> Self {
+        assert!(pieces.pieces.len() <= u64::BITS as usize, "piece sets are limited to 64 pieces");
+        let all_pieces = match pieces.pieces.len() {
+            0 => 0,
+            count => u64::MAX >> (u64::BITS as usize - count),
+        };
+        let starts_len = starts.len();
+        Self {
+            geometry,
+            pieces,
+            colors: starts
+                .into_iter()
+                .map(|start| ColorState {
+                    occupied: BitBoard::new(geometry),
+                    start,
+                    remaining_pieces: all_pieces,
+                    final_piece: None,
+                })
+                .collect(),
+            owners: (0..starts_len).map(ColorOwner::Player).collect(),
+            player_count: starts_len,
+            current_color: 0,
+            turn: 0,
+        }
+    }
+
+    /// Split the colours between `player_count` players
+    pub fn set_owners(&mut self, player_count: usize, owners: Vec<ColorOwner>) {
+        assert_eq!(owners.len(), self.colors.len(), "each colour must have an owner");
+        assert!(
+            owners.iter().all(|owner| match owner {
+                ColorOwner::Player(player) => *player < player_count,
+                ColorOwner::Shared => player_count > 0,
+            }),
+            "colour owned by a player that doesn't exist"
+        );
+        self.owners = owners;
+        self.player_count = player_count;
+    }
+
+    pub fn geometry(&self) -> &GeomT {
+        &self.geometry
+    }
+
+    pub fn pieces(&self) -> &BlokusPieceTable {
+        &self.pieces
+    }
+
+    pub fn color_count(&self) -> usize {
+        self.colors.len()
+    }
+
+    pub fn color(&self, color: usize) -> &ColorState<GeomT, BitsT> {
+        &self.colors[color]
+    }
+
+    /// The colour that places the next piece
+    pub fn current_color(&self) -> usize {
+        self.current_color
+    }
+
+    pub fn player_count(&self) -> usize {
+        self.player_count
+    }
+
+    pub fn color_owner(&self, color: usize) -> ColorOwner {
+        self.owners[color]
+    }
+
+    /// The number of moves played so far, including passes
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    /// The player who places the next piece, either the current colour's owner or, for a shared colour, the next player in rotation
+    pub fn current_player(&self) -> usize {
+        match self.owners[self.current_color] {
+            ColorOwner::Player(player) => player,
+            ColorOwner::Shared => (self.turn / self.colors.len()) % self.player_count,
+        }
+    }
+
+    /// Every tile covered by any colour
+    pub fn occupied(&self) -> BitBoard<GeomT, BitsT> {
+        let mut occupied = BitBoard::new(self.geometry);
+        for color in &self.colors {
+            occupied |= color.occupied.clone();
+        }
+        occupied
+    }
+
+    /// Get the tiles covered by a piece orientation with its top-left corner at `at`,
+    /// or `None` if any part of the piece is off the board.
+    pub fn piece_footprint(&self, piece: usize, orientation: usize, at: Coordinates) -> Option<BitBoard<GeomT, BitsT>> {
+        let shape = &self.pieces.orientation(piece, orientation).shape;
+        let mut footprint = BitBoard::new(self.geometry);
+        for coord in shape.iter_set() {
+            let coord = coord + at;
+            if !footprint.is_coordinate_in_bounds(coord) {
+                return None;
+            }
+            footprint.set(coord, true);
+        }
+        Some(footprint)
+    }
+
+    /// Returns true if `color` may place the given piece orientation with its top-left corner at `at`, ignoring whose turn it is
+    pub fn is_legal_placement(&self, color: usize, piece: usize, orientation: usize, at: Coordinates) -> bool {
+        self.validate_placement(color, piece, orientation, at).is_ok()
+    }
+
+    /// Check if `color` may play `mv` now, and if not, find out why
+    pub fn validate_move(&self, color: usize, mv: Move) -> Result<(), MoveError> {
+        self.check_color(color)?;
+        if color != self.current_color {
+            return Err(MoveError::NotYourTurn {
+                color,
+                current_color: self.current_color,
+            });
+        }
+
+        match mv {
+            Move::Place { piece, orientation, at } => {
+                self.validate_placement(color, piece as usize, orientation as usize, at)
+            }
+            Move::Pass => Ok(()),
+        }
+    }
+
+    /// Check if `color` may place the given piece orientation with its top-left corner at `at`, ignoring whose turn it is
+    pub fn validate_placement(
+        &self,
+        color: usize,
+        piece: usize,
+        orientation: usize,
+        at: Coordinates,
+    ) -> Result<(), MoveError> {
+        self.check_color(color)?;
+        if piece >= self.pieces.pieces.len() || orientation >= self.pieces.count_orientations(piece) {
+            return Err(MoveError::UnknownPiece { piece, orientation });
+        }
+
+        let state = &self.colors[color];
+        if !state.has_piece(piece) {
+            return Err(MoveError::PieceAlreadyPlaced { piece });
+        }
+
+        let occupied = self.occupied();
+        let forbidden = state.forbidden();
+        let mut footprint = BitBoard::new(self.geometry);
+        for coord in self.pieces.orientation(piece, orientation).shape.iter_set() {
+            let coord = coord + at;
+            if !footprint.is_coordinate_in_bounds(coord) {
+                return Err(MoveError::OutOfBounds(coord));
+            }
+            if occupied.get(coord) {
+                return Err(MoveError::Overlap(coord));
+            }
+            if forbidden.get(coord) {
+                return Err(MoveError::TouchesOwnEdge(coord));
+            }
+            footprint.set(coord, true);
+        }
+
+        if footprint.intersects(&state.anchors()) {
+            Ok(())
+        } else if state.is_first_placement() {
+            let start = state.start.first_set().unwrap_or(at);
+            Err(MoveError::StartNotCovered(start))
+        } else {
+            Err(MoveError::NoCornerContact(at))
+        }
+    }
+
+    fn check_color(&self, color: usize) -> Result<(), MoveError> {
+        if color >= self.colors.len() {
+            return Err(MoveError::UnknownColor {
+                color,
+                color_count: self.colors.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Iterate over every legal placement for the current colour
+    pub fn legal_moves(&self) -> LegalMoves<'_, GeomT, BitsT> {
+        LegalMoves::new(self, self.current_color)
+    }
+
+    /// Iterate over every legal placement for `color`, ignoring whose turn it is.
+    /// Nothing is yielded if `color` is not one of the game's colours.
+    pub fn legal_moves_for(&self, color: usize) -> LegalMoves<'_, GeomT, BitsT> {
+        LegalMoves::new(self, color)
+    }
+
+    /// Returns true if `color` could place any piece now, ignoring whose turn it is.
+    /// This stops at the first legal placement found, so it's much cheaper than counting [`Self::legal_moves_for`].
+    /// Returns false if `color` is not one of the game's colours.
+    pub fn has_any_legal_move(&self, color: usize) -> bool {
+        self.legal_moves_for(color).next().is_some()
+    }
+
+    /// The game is over once no colour can place a piece.
+    /// Since pieces are never removed, a colour that can't move now will never move again.
+    pub fn is_game_over(&self) -> bool {
+        (0..self.colors.len()).all(|color| !self.has_any_legal_move(color))
+    }
+
+    /// Check and play a move for the current colour, then pass for each following colour that can't place a piece.
+    ///
+    /// Returns the number of automatic passes, each one is a [`Move::Pass`] that must be undone on its own.
+    pub fn play(&mut self, mv: Move) -> Result<usize, MoveError> {
+        self.validate_move(self.current_color, mv)?;
+        self.apply(mv);
+        Ok(self.pass_stuck_colors())
+    }
+
+    /// Pass for the current colour until it's the turn of a colour that can place a piece.
+    /// If no colour can, every colour passes once and the game is over, with the turn back at the colour that was current.
+    /// Returns the number of passes.
+    pub fn pass_stuck_colors(&mut self) -> usize {
+        let mut passes = 0;
+        while passes < self.colors.len() && !self.has_any_legal_move(self.current_color) {
+            self.apply(Move::Pass);
+            passes += 1;
+        }
+        passes
+    }
+
+    /// Place a piece for the current colour and pass the turn to the next colour.
+    /// Returns false, leaving the game unchanged, if the placement is not legal.
+    pub fn place_piece(&mut self, piece: usize, orientation: usize, at: Coordinates) -> bool {
+        if !self.is_legal_placement(self.current_color, piece, orientation, at) {
+            return false;
+        }
+
+        self.apply(Move::Place {
+            piece: piece as u8,
+            orientation: orientation as u8,
+            at,
+        });
+        true
+    }
+
+    /// Play a move for the current colour and pass the turn to the next colour.
+    ///
+    /// The move is not checked, it should come from [`Self::legal_moves`] or be checked with [`Self::is_legal_placement`].
+    pub fn apply(&mut self, mv: Move) {
+        if let Move::Place { piece, orientation, at } = mv {
+            let (piece, orientation) = (piece as usize, orientation as usize);
+            debug_assert!(self.is_legal_placement(self.current_color, piece, orientation, at));
+
+            let footprint = self.piece_footprint(piece, orientation, at).expect("legal placements are on the board");
+            let state = &mut self.colors[self.current_color];
+            state.occupied |= footprint;
+            state.remaining_pieces &= !(1 << piece);
+            if state.remaining_pieces == 0 {
+                state.final_piece = Some(piece as u8);
+            }
+        }
+        self.current_color = (self.current_color + 1) % self.colors.len();
+        self.turn += 1;
+    }
+
+    /// Take back a move applied with [`Self::apply`], giving the turn back to the colour that played it.
+    ///
+    /// `mv` must be the last move applied, undoing moves out of order leaves the game in an inconsistent state.
+    pub fn undo(&mut self, mv: Move) {
+        self.current_color = (self.current_color + self.colors.len() - 1) % self.colors.len();
+        self.turn -= 1;
+        if let Move::Place { piece, orientation, at } = mv {
+            let (piece, orientation) = (piece as usize, orientation as usize);
+            let footprint = self.piece_footprint(piece, orientation, at).expect("applied placements are on the board");
+            let state = &mut self.colors[self.current_color];
+            debug_assert!(footprint.is_subset_of(&state.occupied) && !state.has_piece(piece));
+
+            state.occupied = state.occupied.and_not(&footprint);
+            state.remaining_pieces |= 1 << piece;
+            state.final_piece = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BlokusRuleset, ColorOwner, DuoBlokus, StandardBlokus};
+    use crate::{
+        bitboard::{Coordinates, StaticGeometry},
+        ruleset::{BlokusPieceSet, DominoPieceSet, Move, MoveError, StandardBlokusPieceSet},
+    };
+
+    #[test]
+    fn first_piece_must_cover_start_corner() {
+        let mut game = StandardBlokus::standard();
+        assert!(!game.place_piece(0, 0, Coordinates::new(1, 1)));
+        assert!(game.place_piece(0, 0, Coordinates::new(0, 0)));
+        assert_eq!(game.current_color(), 1);
+        assert!(!game.color(0).has_piece(0));
+
+        // the second colour starts in the top-right corner
+        assert!(!game.place_piece(1, 0, Coordinates::new(0, 1)));
+        assert!(game.place_piece(1, 0, Coordinates::new(18, 0)));
+    }
+
+    #[test]
+    fn pieces_touch_own_colour_at_corners_only() {
+        let mut game = StandardBlokus::standard();
+        assert!(game.place_piece(0, 0, Coordinates::new(0, 0)));
+        assert!(game.place_piece(0, 0, Coordinates::new(19, 0)));
+        assert!(game.place_piece(0, 0, Coordinates::new(19, 19)));
+        assert!(game.place_piece(0, 0, Coordinates::new(0, 19)));
+
+        // shares an edge with the monomino in the top-left corner
+        assert!(!game.is_legal_placement(0, 1, 0, Coordinates::new(1, 0)));
+        // doesn't touch the monomino at all
+        assert!(!game.is_legal_placement(0, 1, 0, Coordinates::new(2, 1)));
+        // already placed
+        assert!(!game.is_legal_placement(0, 0, 0, Coordinates::new(1, 1)));
+        // off the board
+        assert!(!game.is_legal_placement(0, 4, 0, Coordinates::new(17, 1)));
+
+        assert!(game.place_piece(1, 0, Coordinates::new(1, 1)));
+    }
+
+    #[test]
+    fn colours_may_share_edges_with_each_other() {
+        let mut game: BlokusRuleset<_, u32> = BlokusRuleset::with_start_tiles(
+            StaticGeometry::<5, 5>,
+            StandardBlokusPieceSet::table(),
+            &[Coordinates::new(0, 0), Coordinates::new(3, 0)],
+        );
+        assert!(game.place_piece(2, 0, Coordinates::new(0, 0)));
+        assert!(game.place_piece(0, 0, Coordinates::new(3, 0)));
+        assert!(game.occupied().get(Coordinates::new(2, 0)));
+        assert!(game.occupied().get(Coordinates::new(3, 0)));
+    }
+
+    #[test]
+    fn undo_restores_the_previous_position() {
+        let mut game = StandardBlokus::standard();
+        let mut history = vec![game.clone()];
+        let mut moves = Vec::new();
+        for turn in 0..5 {
+            let mv = match turn {
+                1 => Move::Pass,
+                _ => game.legal_moves().last().expect("expected a legal placement"),
+            };
+            game.apply(mv);
+            moves.push(mv);
+            history.push(game.clone());
+        }
+        assert_eq!(game.current_color(), 1);
+        assert_eq!(game.color(0).remaining_pieces().count_ones(), 19);
+        assert!(game.color(1).is_first_placement());
+
+        for mv in moves.into_iter().rev() {
+            history.pop();
+            game.undo(mv);
+            assert_eq!(&game, history.last().unwrap());
+        }
+    }
+
+    #[test]
+    fn validate_move_explains_illegal_moves() {
+        let mut game = StandardBlokus::standard();
+        let place = |piece, orientation, x, y| Move::Place {
+            piece,
+            orientation,
+            at: Coordinates::new(x, y),
+        };
+
+        assert_eq!(
+            game.validate_move(1, Move::Pass),
+            Err(MoveError::NotYourTurn {
+                color: 1,
+                current_color: 0
+            })
+        );
+        assert_eq!(
+            game.validate_move(0, place(0, 1, 0, 0)),
+            Err(MoveError::UnknownPiece {
+                piece: 0,
+                orientation: 1
+            })
+        );
+        assert_eq!(
+            game.validate_move(0, place(0, 0, 1, 1)),
+            Err(MoveError::StartNotCovered(Coordinates::new(0, 0)))
+        );
+        assert_eq!(
+            game.validate_move(0, place(0, 0, 20, 0)),
+            Err(MoveError::OutOfBounds(Coordinates::new(20, 0)))
+        );
+        assert_eq!(game.validate_move(0, place(0, 0, 0, 0)), Ok(()));
+
+        game.apply(place(0, 0, 0, 0));
+        game.apply(place(0, 0, 19, 0));
+        game.apply(place(0, 0, 19, 19));
+        game.apply(place(0, 0, 0, 19));
+
+        assert_eq!(
+            game.validate_move(0, place(0, 0, 1, 1)),
+            Err(MoveError::PieceAlreadyPlaced { piece: 0 })
+        );
+        assert_eq!(
+            game.validate_move(0, place(2, 0, 0, 0)),
+            Err(MoveError::Overlap(Coordinates::new(0, 0)))
+        );
+        assert_eq!(
+            game.validate_move(0, place(1, 0, 1, 0)),
+            Err(MoveError::TouchesOwnEdge(Coordinates::new(1, 0)))
+        );
+        assert_eq!(
+            game.validate_move(0, place(1, 0, 5, 5)),
+            Err(MoveError::NoCornerContact(Coordinates::new(5, 5)))
+        );
+    }
+
+    #[test]
+    fn unknown_colours_are_errors() {
+        let game = StandardBlokus::standard();
+        let unknown = Err(MoveError::UnknownColor { color: 4, color_count: 4 });
+        assert_eq!(game.validate_move(4, Move::Pass), unknown);
+        assert_eq!(game.validate_placement(4, 0, 0, Coordinates::new(0, 0)), unknown);
+        assert!(!game.is_legal_placement(4, 0, 0, Coordinates::new(0, 0)));
+        assert_eq!(game.legal_moves_for(4).count(), 0);
+        assert!(!game.has_any_legal_move(usize::MAX));
+    }
+
+    #[test]
+    fn duo_starts_near_the_centre() {
+        let mut game = DuoBlokus::duo();
+        assert_eq!(game.color_count(), 2);
+        assert_eq!(game.pieces().pieces.len(), 21);
+
+        // the monomino can only go on the start tile, the X pentomino can cover it with any of its 5 tiles
+        let first_moves: Vec<_> = game.legal_moves().collect();
+        assert_eq!(first_moves.iter().filter(|mv| matches!(mv, Move::Place { piece: 0, .. })).count(), 1);
+        assert_eq!(first_moves.iter().filter(|mv| matches!(mv, Move::Place { piece: 9, .. })).count(), 5);
+
+        assert!(!game.place_piece(0, 0, Coordinates::new(0, 0)));
+        assert!(game.place_piece(9, 0, Coordinates::new(3, 3)));
+        assert_eq!(
+            game.validate_move(
+                1,
+                Move::Place {
+                    piece: 9,
+                    orientation: 0,
+                    at: Coordinates::new(3, 3)
+                }
+            ),
+            Err(MoveError::Overlap(Coordinates::new(4, 3)))
+        );
+        assert!(game.place_piece(9, 0, Coordinates::new(8, 8)));
+        assert_eq!(game.current_color(), 0);
+    }
+
+    #[test]
+    fn stuck_colours_pass_automatically() {
+        // the second colour's start tile is next to the first colour's
+        let mut game: BlokusRuleset<_, u32> = BlokusRuleset::with_start_tiles(
+            StaticGeometry::<3, 3>,
+            DominoPieceSet::table(),
+            &[Coordinates::new(0, 0), Coordinates::new(1, 0), Coordinates::new(2, 2)],
+        );
+        assert!(game.has_any_legal_move(1));
+
+        let horizontal = (0..2).find(|&o| game.pieces().orientation(0, o).shape.geometry().width == 2).unwrap();
+        let cover_both_starts = Move::Place {
+            piece: 0,
+            orientation: horizontal as u8,
+            at: Coordinates::new(0, 0),
+        };
+        assert_eq!(game.play(cover_both_starts), Ok(1));
+        assert!(!game.has_any_legal_move(1));
+        assert_eq!(game.current_color(), 2);
+        assert!(!game.is_game_over());
+
+        // nobody can move after this, so every colour passes once
+        let last = game.legal_moves().next().unwrap();
+        let turn = game.turn();
+        assert_eq!(game.play(last), Ok(3));
+        assert!(game.is_game_over());
+        assert_eq!(game.current_color(), 0);
+        assert_eq!(game.turn(), turn + 4);
+
+        for _ in 0..3 {
+            game.undo(Move::Pass);
+        }
+        game.undo(last);
+        assert_eq!(game.current_color(), 2);
+        assert!(!game.is_game_over());
+    }
+
+    #[test]
+    fn shared_colour_rotates_between_players() {
+        let mut game = StandardBlokus::three_player();
+        assert_eq!(game.player_count(), 3);
+        assert_eq!(game.color_owner(3), ColorOwner::Shared);
+
+        let mut shared_players = Vec::new();
+        for _ in 0..4 {
+            for color in 0..4 {
+                assert_eq!(game.current_color(), color);
+                if color == 3 {
+                    shared_players.push(game.current_player());
+                } else {
+                    assert_eq!(game.current_player(), color);
+                }
+                game.apply(Move::Pass);
+            }
+        }
+        assert_eq!(shared_players, vec![0, 1, 2, 0]);
+        assert_eq!(game.turn(), 16);
+
+        game.undo(Move::Pass);
+        assert_eq!(game.current_player(), 0);
+    }
+}
@@ -0,0 +1,61 @@
+use std::any::TypeId;
+use std::sync::Mutex;
+
+use super::{BlokusPiece, BlokusPieceTable};
+use crate::bitboard::Coordinates;
+
+/// A set of pieces, with every orientation and corner cell computed the first time the set's table is used.
+///
+/// With the `nightly` feature the orientations and corners are also associated consts, like `ORIENTATIONS` and `CORNERS`.
+/// Those can't be computed on stable, use the functions of the same name, like [`BlokusPieceSet::orientations`], instead.
+pub trait BlokusPieceSet<const N: usize>: 'static {
+    const PIECES: [BlokusPiece; N];
+
+    /// This set's pieces, orientations and corners, in a form that can be stored in a game.
+    /// The table is built by [`BlokusPieceTable::from_pieces`] on the first call, later calls return the same table.
+    fn table() -> BlokusPieceTable {
+        // statics are shared by every implementation, so the tables are keyed by the set's type
+        static TABLES: Mutex<Vec<(TypeId, BlokusPieceTable)>> = Mutex::new(Vec::new());
+
+        let mut tables = TABLES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((_, table)) = tables.iter().find(|(id, _)| *id == TypeId::of::<Self>()) {
+            return *table;
+        }
+
+        let table = BlokusPieceTable::from_pieces(&Self::PIECES);
+        tables.push((TypeId::of::<Self>(), table));
+        table
+    }
+
+    /// The total number of distinct orientations of all pieces in the set
+    fn orientation_count() -> usize {
+        Self::table().orientations.len()
+    }
+
+    /// The index into [`BlokusPieceSet::orientations`] of each piece's first orientation
+    fn orientation_offsets() -> &'static [usize] {
+        Self::table().orientation_offsets
+    }
+
+    /// Every distinct orientation of every piece, grouped by piece in the same order as `PIECES`
+    fn orientations() -> &'static [BlokusPiece] {
+        Self::table().orientations
+    }
+
+    /// The total number of corner cells (see [`BlokusPiece::count_corner_cells`]) in every orientation of every piece
+    fn corner_count() -> usize {
+        Self::table().corners.len()
+    }
+
+    /// The index into [`BlokusPieceSet::corners`] of each orientation's first corner cell,
+    /// indexed the same way as [`BlokusPieceSet::orientations`]
+    fn corner_offsets() -> &'static [usize] {
+        Self::table().corner_offsets
+    }
+
+    /// The corner cells of every orientation of every piece, relative to the orientation's top-left corner.
+    /// A new piece can only touch its own colour through one of these cells.
+    fn corners() -> &'static [Coordinates] {
+        Self::table().corners
+    }
+}
@@ -0,0 +1,859 @@
+mod builder;
+#[cfg(feature = "nightly")]
+#[path = "const_piece_set.rs"]
+mod piece_set;
+#[cfg(not(feature = "nightly"))]
+#[path = "lazy_piece_set.rs"]
+mod piece_set;
+mod game;
+mod moves;
+mod notation;
+mod record;
+mod scoring;
+
+pub use builder::*;
+pub use game::*;
+pub use moves::*;
+pub use notation::*;
+pub use piece_set::*;
+pub use record::*;
+pub use scoring::*;
+
+use std::{fmt, str::FromStr};
+
+use crate::bitboard::{BitBoard, BoardGeometry, Coordinates, Direction, DirectionSet, DynGeometry, Transform};
+
+/// A piece set's tables without the set's type, see [`BlokusPieceSet::table`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlokusPieceTable {
+    pub pieces: &'static [BlokusPiece],
+    pub orientation_offsets: &'static [usize],
+    pub orientations: &'static [BlokusPiece],
+    pub corner_offsets: &'static [usize],
+    pub corners: &'static [Coordinates],
+}
+
+impl BlokusPieceTable {
+    /// Compute the orientation and corner tables for `pieces` at runtime.
+    /// The tables are leaked, so they can be stored in games the same way as a piece set's tables.
+    pub fn from_pieces(pieces: &[BlokusPiece]) -> BlokusPieceTable {
+        let mut orientation_offsets = Vec::with_capacity(pieces.len());
+        let mut orientations = Vec::new();
+        for piece in pieces {
+            orientation_offsets.push(orientations.len());
+            orientations.extend((0..piece.count_orientations()).map(|n| piece.nth_orientation(n)));
+        }
+
+        let mut corner_offsets = Vec::with_capacity(orientations.len());
+        let mut corners = Vec::new();
+        for orientation in &orientations {
+            corner_offsets.push(corners.len());
+            corners.extend((0..orientation.count_corner_cells()).map(|n| orientation.nth_corner_cell(n)));
+        }
+
+        BlokusPieceTable {
+            pieces: Vec::leak(pieces.to_vec()),
+            orientation_offsets: Vec::leak(orientation_offsets),
+            orientations: Vec::leak(orientations),
+            corner_offsets: Vec::leak(corner_offsets),
+            corners: Vec::leak(corners),
+        }
+    }
+
+    pub const fn count_orientations(&self, piece: usize) -> usize {
+        if piece + 1 < self.orientation_offsets.len() {
+            self.orientation_offsets[piece + 1] - self.orientation_offsets[piece]
+        } else {
+            self.orientations.len() - self.orientation_offsets[piece]
+        }
+    }
+
+    pub const fn orientation(&self, piece: usize, orientation: usize) -> &BlokusPiece {
+        &self.orientations[self.orientation_index(piece, orientation)]
+    }
+
+    /// Get the corner cells of a piece orientation, see [`BlokusPiece::nth_corner_cell`]
+    pub fn orientation_corners(&self, piece: usize, orientation: usize) -> &'static [Coordinates] {
+        let index = self.orientation_index(piece, orientation);
+        let end = self.corner_offsets.get(index + 1).copied().unwrap_or(self.corners.len());
+        &self.corners[self.corner_offsets[index]..end]
+    }
+
+    const fn orientation_index(&self, piece: usize, orientation: usize) -> usize {
+        assert!(orientation < self.count_orientations(piece), "invalid orientation index");
+        self.orientation_offsets[piece] + orientation
+    }
+}
+
+pub struct StandardBlokusPieceSet;
+impl BlokusPieceSet<21> for StandardBlokusPieceSet {
+    const PIECES: [BlokusPiece; 21] = [
+        BlokusPiece::parse("x"),
+        BlokusPiece::parse("xx"),
+        BlokusPiece::parse("xxx"),
+        BlokusPiece::parse("xxxx"),
+        BlokusPiece::parse("xxxxx"),
+        BlokusPiece::parse(
+            r#"x x x x
+               x"#,
+        ),
+        BlokusPiece::parse(
+            r#"x x x
+               x
+               x"#,
+        ),
+        BlokusPiece::parse(
+            r#"x x x
+               x"#,
+        ),
+        BlokusPiece::parse(
+            r#"x x
+               x"#,
+        ),
+        BlokusPiece::parse(
+            r#". x .
+               x x x
+               . x ."#,
+        ),
+        BlokusPiece::parse(
+            r#". x .
+               . x x
+               x x ."#,
+        ),
+        BlokusPiece::parse(
+            r#"x . .
+               x x x
+               x . ."#,
+        ),
+        BlokusPiece::parse(
+            r#"x .
+               x .
+               x x
+               x ."#,
+        ),
+        BlokusPiece::parse(
+            r#"x .
+               x x
+               x ."#,
+        ),
+        BlokusPiece::parse(
+            r#"x x
+               x .
+               x x"#,
+        ),
+        BlokusPiece::parse(
+            r#"x x
+               x x"#,
+        ),
+        BlokusPiece::parse(
+            r#"x x x
+               x x ."#,
+        ),
+        BlokusPiece::parse(
+            r#". x x
+               . x .
+               x x ."#,
+        ),
+        BlokusPiece::parse(
+            r#". x
+               x x
+               x .
+               x"#,
+        ),
+        BlokusPiece::parse(
+            r#". . x
+               . x x
+               x x ."#,
+        ),
+        BlokusPiece::parse(
+            r#". x
+               x x
+               x ."#,
+        ),
+    ];
+}
+
+/// A set with only the monomino
+pub struct MonominoPieceSet;
+impl BlokusPieceSet<1> for MonominoPieceSet {
+    const PIECES: [BlokusPiece; 1] = [StandardBlokusPieceSet::PIECES[0]];
+}
+
+/// A set with only the domino
+pub struct DominoPieceSet;
+impl BlokusPieceSet<1> for DominoPieceSet {
+    const PIECES: [BlokusPiece; 1] = [StandardBlokusPieceSet::PIECES[1]];
+}
+
+/// A set with only the L (or V) tromino
+pub struct LTrominoPieceSet;
+impl BlokusPieceSet<1> for LTrominoPieceSet {
+    const PIECES: [BlokusPiece; 1] = [StandardBlokusPieceSet::PIECES[8]];
+}
+
+/// A set with only the L tetromino
+pub struct LTetrominoPieceSet;
+impl BlokusPieceSet<1> for LTetrominoPieceSet {
+    const PIECES: [BlokusPiece; 1] = [StandardBlokusPieceSet::PIECES[7]];
+}
+
+/// The five free tetrominoes: I, L, T, O and S
+pub struct TetrominoPieceSet;
+impl BlokusPieceSet<5> for TetrominoPieceSet {
+    const PIECES: [BlokusPiece; 5] = [
+        StandardBlokusPieceSet::PIECES[3],
+        StandardBlokusPieceSet::PIECES[7],
+        StandardBlokusPieceSet::PIECES[13],
+        StandardBlokusPieceSet::PIECES[15],
+        StandardBlokusPieceSet::PIECES[20],
+    ];
+}
+
+/// The twelve free pentominoes
+pub struct PentominoPieceSet;
+impl BlokusPieceSet<12> for PentominoPieceSet {
+    const PIECES: [BlokusPiece; 12] = [
+        StandardBlokusPieceSet::PIECES[4],
+        StandardBlokusPieceSet::PIECES[5],
+        StandardBlokusPieceSet::PIECES[6],
+        StandardBlokusPieceSet::PIECES[9],
+        StandardBlokusPieceSet::PIECES[10],
+        StandardBlokusPieceSet::PIECES[11],
+        StandardBlokusPieceSet::PIECES[12],
+        StandardBlokusPieceSet::PIECES[14],
+        StandardBlokusPieceSet::PIECES[16],
+        StandardBlokusPieceSet::PIECES[17],
+        StandardBlokusPieceSet::PIECES[18],
+        StandardBlokusPieceSet::PIECES[19],
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlokusPiece {
+    pub shape: BitBoard<DynGeometry, u32>,
+}
+
+impl BlokusPiece {
+    const_fn! {
+        /// Get this piece rotated and/or mirrored by `transform`
+        pub fn transform(&self, transform: Transform) -> BlokusPiece {
+            BlokusPiece {
+                shape: self.shape.transform(transform),
+            }
+        }
+    }
+
+    /// Returns true if both pieces have exactly the same cells set, in the same orientation
+    pub const fn same_shape(&self, other: &BlokusPiece) -> bool {
+        self.shape.geometry().width == other.shape.geometry().width
+            && self.shape.geometry().height == other.shape.geometry().height
+            && *self.shape.data() == *other.shape.data()
+    }
+
+    const_fn! {
+        /// Get this piece with any empty rows and columns around its edges removed
+        pub fn normalize(&self) -> BlokusPiece {
+            let width = self.shape.geometry().width();
+            let height = self.shape.geometry().height();
+
+            let mut min = Coordinates::new(width, height);
+            let mut max = Coordinates::new(-1, -1);
+            let mut coord = Coordinates::zero();
+            while coord.y < height {
+                while coord.x < width {
+                    if self.shape.get(coord) {
+                        if coord.x < min.x {
+                            min.x = coord.x;
+                        }
+                        if coord.y < min.y {
+                            min.y = coord.y;
+                        }
+                        if coord.x > max.x {
+                            max.x = coord.x;
+                        }
+                        if coord.y > max.y {
+                            max.y = coord.y;
+                        }
+                    }
+                    coord.x += 1;
+                }
+                coord.x = 0;
+                coord.y += 1;
+            }
+
+            if max.x < 0 {
+                return BlokusPiece {
+                    shape: BitBoard::new_with_data(DynGeometry::new(0, 0), 0),
+                };
+            }
+
+            let normalized_width = max.x - min.x + 1;
+            let mut data = 0u32;
+            coord = min;
+            while coord.y <= max.y {
+                while coord.x <= max.x {
+                    if self.shape.get(coord) {
+                        data |= 1u32 << (normalized_width * (coord.y - min.y) + coord.x - min.x);
+                    }
+                    coord.x += 1;
+                }
+                coord.x = min.x;
+                coord.y += 1;
+            }
+
+            BlokusPiece {
+                shape: BitBoard::new_with_data(DynGeometry::new(normalized_width, max.y - min.y + 1), data),
+            }
+        }
+
+        /// Get this piece normalized and then transformed by each transform in `Transform::ALL`, in the same order
+        fn all_transforms(&self) -> [BlokusPiece; 8] {
+            let base = self.normalize();
+            let mut transformed = [base; 8];
+            let mut i = 1;
+            while i < Transform::ALL.len() {
+                transformed[i] = base.transform(Transform::ALL[i]);
+                i += 1;
+            }
+            transformed
+        }
+
+        /// Returns true if `transformed[i]` has a shape that no earlier transform does
+        fn is_distinct_orientation(transformed: &[BlokusPiece; 8], i: usize) -> bool {
+            let mut j = 0;
+            while j < i {
+                if transformed[j].same_shape(&transformed[i]) {
+                    return false;
+                }
+                j += 1;
+            }
+            true
+        }
+
+        /// Count the distinct fixed orientations of this piece, this is 1, 2, 4 or 8 depending on its symmetry
+        pub fn count_orientations(&self) -> usize {
+            let transformed = self.all_transforms();
+            let mut count = 0;
+            let mut i = 0;
+            while i < Transform::ALL.len() {
+                if Self::is_distinct_orientation(&transformed, i) {
+                    count += 1;
+                }
+                i += 1;
+            }
+            count
+        }
+
+        /// Get the index into `Transform::ALL` of the transform that produces this piece's `n`th distinct orientation
+        fn nth_orientation_index(transformed: &[BlokusPiece; 8], n: usize) -> usize {
+            let mut remaining = n;
+            let mut i = 0;
+            while i < Transform::ALL.len() {
+                if Self::is_distinct_orientation(transformed, i) {
+                    if remaining == 0 {
+                        return i;
+                    }
+                    remaining -= 1;
+                }
+                i += 1;
+            }
+            panic!("invalid orientation index");
+        }
+
+        /// Get the transform that produces this piece's `n`th distinct orientation.
+        /// Orientations are ordered by the first transform in `Transform::ALL` that produces them.
+        pub fn nth_orientation_transform(&self, n: usize) -> Transform {
+            Transform::ALL[Self::nth_orientation_index(&self.all_transforms(), n)]
+        }
+
+        /// Get this piece's `n`th distinct orientation, normalized to the top-left
+        pub fn nth_orientation(&self, n: usize) -> BlokusPiece {
+            let transformed = self.all_transforms();
+            transformed[Self::nth_orientation_index(&transformed, n)]
+        }
+
+        /// Returns true if `coord` is set, and the tiles touching its `diagonal` corner are not
+        fn is_open_corner(&self, coord: Coordinates, diagonal: Direction) -> bool {
+            let corner_mask = diagonal.components().union_c(
+                DirectionSet::from_bits_truncate_c(diagonal as u8, DirectionSet::CONST_TOKEN),
+            );
+            self.shape.get(coord) && self.shape.are_adjacent_tiles_set(coord, corner_mask).bits_c() == 0
+        }
+
+        pub fn nth_corner(&self, n: usize) -> Coordinates {
+            let mut corners = n;
+            let mut coord = Coordinates::zero();
+            while coord.x < self.shape.geometry().width() {
+                while coord.y < self.shape.geometry().height() {
+                    let mut i = 0;
+                    while i < 4 {
+                        if self.is_open_corner(coord, Direction::DIAGONAL[i]) {
+                            if corners == 0 {
+                                return coord + Direction::DIAGONAL[i].as_coordinates();
+                            } else {
+                                corners -= 1;
+                            }
+                        }
+                        i += 1;
+                    }
+                    coord.y += 1;
+                }
+                coord.y = 0;
+                coord.x += 1;
+            }
+            panic!("invalid corner index");
+        }
+
+        pub fn count_corners(&self) -> usize {
+            let mut corners = 0;
+            let mut coord = Coordinates::zero();
+            while coord.x < self.shape.geometry().width() {
+                while coord.y < self.shape.geometry().height() {
+                    let mut i = 0;
+                    while i < 4 {
+                        if self.is_open_corner(coord, Direction::DIAGONAL[i]) {
+                            corners += 1;
+                        }
+                        i += 1;
+                    }
+                    coord.y += 1;
+                }
+                coord.y = 0;
+                coord.x += 1;
+            }
+            corners
+        }
+
+        /// Returns true if `coord` is set and has at least one open corner
+        fn is_corner_cell(&self, coord: Coordinates) -> bool {
+            let mut i = 0;
+            while i < 4 {
+                if self.is_open_corner(coord, Direction::DIAGONAL[i]) {
+                    return true;
+                }
+                i += 1;
+            }
+            false
+        }
+
+        /// Get the `n`th corner cell: a set cell with at least one open corner, so it may touch another piece of the same colour
+        pub fn nth_corner_cell(&self, n: usize) -> Coordinates {
+            let mut cells = n;
+            let mut coord = Coordinates::zero();
+            while coord.x < self.shape.geometry().width() {
+                while coord.y < self.shape.geometry().height() {
+                    if self.is_corner_cell(coord) {
+                        if cells == 0 {
+                            return coord;
+                        } else {
+                            cells -= 1;
+                        }
+                    }
+                    coord.y += 1;
+                }
+                coord.y = 0;
+                coord.x += 1;
+            }
+            panic!("invalid corner cell index");
+        }
+
+        /// Count the set cells with at least one open corner, unlike [`BlokusPiece::count_corners`] each cell is only counted once
+        pub fn count_corner_cells(&self) -> usize {
+            let mut cells = 0;
+            let mut coord = Coordinates::zero();
+            while coord.x < self.shape.geometry().width() {
+                while coord.y < self.shape.geometry().height() {
+                    if self.is_corner_cell(coord) {
+                        cells += 1;
+                    }
+                    coord.y += 1;
+                }
+                coord.y = 0;
+                coord.x += 1;
+            }
+            cells
+        }
+    }
+
+    /// Parse a piece from rows of `x` (set) and `.` (empty) cells, separated by `|` or newlines. Spaces are ignored.
+    ///
+    /// This is meant for building piece tables at compile time, it panics on any other character (including non-ascii text)
+    /// or if the shape is larger than 32 cells. Use [`BlokusPiece::try_parse`] for text that isn't known ahead of time.
+    pub const fn parse(str: &'static str) -> BlokusPiece {
+        let str_b = str.as_bytes();
+
+        let mut columns = 1;
+        let mut rows = 1;
+
+        // determine row and column counts
+        {
+            let mut i = 0;
+            let mut current_col_count = 0;
+            while i < str.len() {
+                match str_b[i] {
+                    b'|' | b'\n' => {
+                        rows += 1;
+                        if current_col_count > columns {
+                            columns = current_col_count
+                        }
+                        current_col_count = 0;
+                    }
+                    b'x' | b'.' => {
+                        current_col_count += 1;
+                    }
+                    b' ' => (),
+                    _ => panic!("invalid character in blokus piece data"),
+                }
+
+                i += 1;
+            }
+
+            if current_col_count > columns {
+                columns = current_col_count
+            }
+        }
+
+        if columns * rows > u32::BITS as i16 {
+            panic!("blokus piece data has more than 32 cells");
+        }
+
+        // set up board data
+        let mut board_data = 0u32;
+        {
+            let mut i = 0;
+            let mut x = 0;
+            let mut y = 0;
+            while i < str.len() {
+                match str_b[i] {
+                    b'|' | b'\n' => {
+                        x = 0;
+                        y += 1;
+                    }
+                    b'x' | b'.' => {
+                        if str_b[i] == b'x' {
+                            board_data |= 1u32 << (columns * y + x);
+                        }
+                        x += 1;
+                    }
+                    b' ' => (),
+                    _ => panic!("invalid character in blokus piece data"),
+                }
+
+                i += 1;
+            }
+        }
+
+        BlokusPiece {
+            shape: BitBoard::new_with_data(DynGeometry::new(columns, rows), board_data),
+        }
+    }
+
+    /// Parse a piece in the same format as [`BlokusPiece::parse`], returning an error instead of panicking.
+    /// The shape must have at least one cell set, and every set cell must share an edge with another.
+    /// Carriage returns and trailing empty rows are ignored, so text ending in a newline or using CRLF parses the same.
+    pub fn try_parse(text: &str) -> Result<BlokusPiece, ParsePieceError> {
+        let mut rows: Vec<Vec<bool>> = vec![Vec::new()];
+        let mut line = 1;
+        let mut column = 1;
+        for character in text.chars() {
+            match character {
+                '|' | '\n' => {
+                    rows.push(Vec::new());
+                    line += 1;
+                    column = 0;
+                }
+                'x' => rows.last_mut().unwrap().push(true),
+                '.' => rows.last_mut().unwrap().push(false),
+                ' ' | '\r' => (),
+                _ => return Err(ParsePieceError::InvalidCharacter { line, column, character }),
+            }
+            column += 1;
+        }
+        while rows.len() > 1 && rows.last().is_some_and(Vec::is_empty) {
+            rows.pop();
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+        if width * height > u32::BITS as usize {
+            return Err(ParsePieceError::TooLarge { width, height });
+        }
+
+        let mut data = 0u32;
+        for (y, row) in rows.iter().enumerate() {
+            for (x, _) in row.iter().enumerate().filter(|(_, set)| **set) {
+                data |= 1 << (width * y + x);
+            }
+        }
+
+        let shape = BitBoard::new_with_data(DynGeometry::new(width as i16, height as i16), data);
+        let Some(first) = shape.first_set() else {
+            return Err(ParsePieceError::Empty);
+        };
+
+        // grow a region from the first cell until it stops changing, then check it covers the whole shape
+        let mut region = BitBoard::new(*shape.geometry());
+        region.set(first, true);
+        loop {
+            let grown = region | (region.orthogonal_neighbors() & shape);
+            if grown == region {
+                break;
+            }
+            region = grown;
+        }
+        if region != shape {
+            return Err(ParsePieceError::Disconnected);
+        }
+
+        Ok(BlokusPiece { shape })
+    }
+}
+
+
+impl FromStr for BlokusPiece {
+    type Err = ParsePieceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BlokusPiece::try_parse(s)
+    }
+}
+
+/// Reasons text can't be parsed as a piece, see [`BlokusPiece::try_parse`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePieceError {
+    /// a character other than `x`, `.`, space, `|`, a newline or a carriage return, at a 1-based line and column.
+    /// Rows separated by `|` count as separate lines.
+    InvalidCharacter { line: usize, column: usize, character: char },
+
+    /// the rows and columns span more than the 32 cells a piece can hold
+    TooLarge { width: usize, height: usize },
+
+    /// no cells are set
+    Empty,
+
+    /// some set cells don't share an edge with the rest of the piece
+    Disconnected,
+}
+
+impl fmt::Display for ParsePieceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParsePieceError::InvalidCharacter { line, column, character } => {
+                write!(f, "invalid character {:?} at line {}, column {}", character, line, column)
+            }
+            ParsePieceError::TooLarge { width, height } => {
+                write!(f, "piece is {}x{}, but can have at most 32 cells", width, height)
+            }
+            ParsePieceError::Empty => write!(f, "piece has no cells"),
+            ParsePieceError::Disconnected => write!(f, "piece cells are not all connected"),
+        }
+    }
+}
+
+impl std::error::Error for ParsePieceError {}
+
+#[cfg(test)]
+mod test {
+    use crate::bitboard::{BoardGeometry, Coordinates, Transform};
+
+    use super::{
+        BlokusPiece, BlokusPieceSet, BlokusPieceTable, DominoPieceSet, ParsePieceError, LTetrominoPieceSet, LTrominoPieceSet, MonominoPieceSet,
+        PentominoPieceSet, StandardBlokusPieceSet, TetrominoPieceSet,
+    };
+
+    #[test]
+    fn standard_blokus_pieces_have_at_most_5_tiles() {
+        for (i, piece) in StandardBlokusPieceSet::PIECES.iter().enumerate() {
+            let tile_count = piece.shape.data().count_ones();
+            assert!(
+                tile_count <= 5,
+                "piece #{i} has {tile_count} tiles, expected at most 5",
+            )
+        }
+    }
+
+    #[test]
+    fn standard_blokus_pieces_include_all_expected_dimensions() {
+        let mut dimen = vec![
+            (3, 3),
+            (4, 2),
+            (3, 2),
+            (2, 2),
+            (3, 3),
+            (3, 3),
+            (3, 3),
+            (2, 3),
+            (2, 4),
+            (2, 3),
+            (2, 2),
+            (3, 2),
+            (1, 1),
+            (2, 1),
+            (3, 1),
+            (4, 1),
+            (5, 1),
+            (3, 3),
+            (2, 4),
+            (3, 3),
+            (2, 3),
+        ];
+
+        for (i, piece) in StandardBlokusPieceSet::PIECES.iter().enumerate() {
+            let geom = piece.shape.geometry();
+            let index = dimen
+                .iter()
+                .enumerate()
+                .find(|(_, p)| p.0 == geom.width() && p.1 == geom.height())
+                .map(|(i, _)| i);
+            match index {
+                Some(index) => {
+                    dimen.remove(index);
+                }
+                None => panic!(
+                    "piece #{i} is {w}x{h}, which is not in dimension list\n  dimen = {dimen:#?}",
+                    w = geom.width(),
+                    h = geom.height()
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn transformed_pieces_keep_their_tile_count() {
+        for piece in StandardBlokusPieceSet::PIECES.iter() {
+            for transform in Transform::ALL {
+                let transformed = piece.transform(transform);
+                assert_eq!(transformed.shape.data().count_ones(), piece.shape.data().count_ones());
+                assert_eq!(transformed.transform(transform.inverse()), *piece);
+            }
+        }
+    }
+
+    #[test]
+    fn standard_blokus_pieces_have_91_orientations() {
+        let table = StandardBlokusPieceSet::table();
+        assert_eq!(table.orientations.len(), 91);
+
+        let counts: Vec<usize> = StandardBlokusPieceSet::PIECES.iter().map(|p| p.count_orientations()).collect();
+        assert_eq!(counts, vec![1, 2, 2, 2, 2, 8, 4, 8, 4, 1, 8, 4, 8, 4, 4, 1, 8, 4, 8, 4, 4]);
+
+        for (i, piece) in StandardBlokusPieceSet::PIECES.iter().enumerate() {
+            let offset = table.orientation_offsets[i];
+            let orientations = &table.orientations[offset..offset + piece.count_orientations()];
+            for (a, orientation) in orientations.iter().enumerate() {
+                assert!(orientation.same_shape(&orientation.normalize()));
+                assert!(orientations[..a].iter().all(|other| !other.same_shape(orientation)));
+            }
+        }
+    }
+
+    #[test]
+    fn small_piece_sets_derive_their_tables() {
+        assert_eq!(MonominoPieceSet::orientation_count(), 1);
+        assert_eq!(DominoPieceSet::orientation_count(), 2);
+        assert_eq!(LTrominoPieceSet::orientation_count(), 4);
+        assert_eq!(LTetrominoPieceSet::orientation_count(), 8);
+        assert_eq!(TetrominoPieceSet::orientation_count(), 19);
+        assert_eq!(PentominoPieceSet::orientation_count(), 63);
+
+        assert_eq!(MonominoPieceSet::corner_count(), 1);
+        assert_eq!(MonominoPieceSet::corners(), &[Coordinates::new(0, 0)]);
+        assert_eq!(TetrominoPieceSet::corner_offsets().len(), 19);
+        assert_eq!(TetrominoPieceSet::orientation_offsets(), &[0, 2, 10, 14, 15]);
+        for piece in PentominoPieceSet::PIECES.iter() {
+            assert_eq!(piece.shape.data().count_ones(), 5);
+        }
+    }
+
+    #[test]
+    fn runtime_tables_match_piece_set_tables() {
+        let table = BlokusPieceTable::from_pieces(&StandardBlokusPieceSet::PIECES);
+        assert_eq!(table, StandardBlokusPieceSet::table());
+        assert_eq!(StandardBlokusPieceSet::table(), StandardBlokusPieceSet::table());
+    }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn const_tables_match_table_functions() {
+        assert_eq!(StandardBlokusPieceSet::ORIENTATION_COUNT, StandardBlokusPieceSet::orientation_count());
+        assert_eq!(&StandardBlokusPieceSet::ORIENTATIONS[..], StandardBlokusPieceSet::orientations());
+        assert_eq!(&StandardBlokusPieceSet::CORNERS[..], StandardBlokusPieceSet::corners());
+        assert_eq!(&StandardBlokusPieceSet::CORNER_OFFSETS[..], StandardBlokusPieceSet::corner_offsets());
+    }
+
+    #[test]
+    fn normalize_removes_empty_border() {
+        let padded = BlokusPiece::parse(". . .|. x x|. x .");
+        assert!(padded.normalize().same_shape(&BlokusPiece::parse("x x|x .")));
+    }
+
+    #[test]
+    fn count_corners_of_l_tromino() {
+        // x x
+        // x .
+        let l3 = BlokusPiece::parse("x x|x .");
+        assert_eq!(l3.count_corners(), 5);
+        assert_eq!(l3.count_corner_cells(), 3);
+        assert_eq!(l3.nth_corner_cell(0), Coordinates::new(0, 0));
+        assert_eq!(l3.nth_corner_cell(1), Coordinates::new(0, 1));
+        assert_eq!(l3.nth_corner_cell(2), Coordinates::new(1, 0));
+
+        let plus = BlokusPiece::parse(". x .|x x x|. x .");
+        assert_eq!(plus.count_corners(), 8);
+        assert_eq!(plus.count_corner_cells(), 4);
+    }
+
+    #[test]
+    fn correctly_count_1x1_corners() {
+        let _1x1 = BlokusPiece::parse("x");
+        assert_eq!(_1x1.count_corners(), 4);
+
+        let mut corners: Vec<Coordinates> = (0..4).map(|n| _1x1.nth_corner(n)).collect();
+        corners.sort_by_key(|coord| (coord.x, coord.y));
+        assert_eq!(
+            corners,
+            vec![Coordinates::new(-1, -1), Coordinates::new(-1, 1), Coordinates::new(1, -1), Coordinates::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn try_parse_matches_parse() {
+        for text in ["x", "x x x x|x", "x x .\n. x x\n. x .", ". . .|. x x|. x ."] {
+            let piece: BlokusPiece = text.parse().unwrap();
+            assert_eq!(piece, BlokusPiece::parse(text));
+        }
+    }
+
+    #[test]
+    fn try_parse_ignores_line_endings() {
+        assert_eq!(BlokusPiece::try_parse("x x\n"), Ok(BlokusPiece::parse("x x")));
+        assert_eq!(BlokusPiece::try_parse("x x|"), Ok(BlokusPiece::parse("x x")));
+        assert_eq!(BlokusPiece::try_parse("x x\n. x\n\n"), Ok(BlokusPiece::parse("x x\n. x")));
+        assert_eq!(BlokusPiece::try_parse("x x\r\n. x\r\n"), Ok(BlokusPiece::parse("x x\n. x")));
+    }
+
+    #[test]
+    fn try_parse_errors() {
+        assert_eq!(
+            BlokusPiece::try_parse("x x|x o"),
+            Err(ParsePieceError::InvalidCharacter { line: 2, column: 3, character: 'o' })
+        );
+        assert_eq!(
+            BlokusPiece::try_parse("x\nx×"),
+            Err(ParsePieceError::InvalidCharacter { line: 2, column: 2, character: '×' })
+        );
+        assert_eq!(
+            BlokusPiece::try_parse("xxxxxxxxx|xxxxxxxxx|xxxxxxxxx|xxxxxxxxx"),
+            Err(ParsePieceError::TooLarge { width: 9, height: 4 })
+        );
+        assert_eq!(BlokusPiece::try_parse(". .|. ."), Err(ParsePieceError::Empty));
+        assert_eq!(BlokusPiece::try_parse(""), Err(ParsePieceError::Empty));
+        assert_eq!(BlokusPiece::try_parse("x .|. x"), Err(ParsePieceError::Disconnected));
+        assert_eq!(BlokusPiece::try_parse("x x x|x . x|x . ."), Ok(BlokusPiece::parse("x x x|x . x|x . .")));
+        assert_eq!(BlokusPiece::try_parse(&"x".repeat(32)).map(|piece| piece.shape.data().count_ones()), Ok(32));
+        assert_eq!(
+            BlokusPiece::try_parse(&"x".repeat(33)),
+            Err(ParsePieceError::TooLarge { width: 33, height: 1 })
+        );
+    }
+}
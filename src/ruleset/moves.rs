@@ -0,0 +1,336 @@
+use std::fmt;
+
+use crate::bitboard::{BitArray, BitBoard, BitBoardCells, BoardGeometry, Coordinates};
+
+use super::BlokusRuleset;
+
+/// A single turn in a game of Blokus, small enough to store in move lists and use as a table key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Move {
+    /// Place a piece from the game's piece table
+    Place {
+        /// index into the piece table's pieces
+        piece: u8,
+
+        /// index into the piece's distinct orientations
+        orientation: u8,
+
+        /// where the top-left corner of the orientation's bounding box is placed
+        at: Coordinates,
+    },
+
+    /// Skip a turn without placing a piece
+    Pass,
+}
+
+/// The reason a move is not legal, returned by [`BlokusRuleset::validate_move`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MoveError {
+    /// the colour index is not one of the game's colours
+    UnknownColor { color: usize, color_count: usize },
+
+    /// the colour can't move, because it's another colour's turn
+    NotYourTurn { color: usize, current_color: usize },
+
+    /// the piece or orientation index is not in the game's piece table
+    UnknownPiece { piece: usize, orientation: usize },
+
+    /// the colour has already placed this piece
+    PieceAlreadyPlaced { piece: usize },
+
+    /// a tile of the piece would be off the board
+    OutOfBounds(Coordinates),
+
+    /// a tile of the piece would cover a tile that is already covered
+    Overlap(Coordinates),
+
+    /// a tile of the piece would share an edge with one of the colour's own pieces
+    TouchesOwnEdge(Coordinates),
+
+    /// the piece, placed with its top-left corner at these coordinates, doesn't touch one of the colour's pieces at a corner
+    NoCornerContact(Coordinates),
+
+    /// the colour's first piece doesn't cover its start tile
+    StartNotCovered(Coordinates),
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::UnknownColor { color, color_count } => {
+                write!(f, "there is no colour {}, the game has {} colours", color, color_count)
+            }
+            MoveError::NotYourTurn { color, current_color } => {
+                write!(f, "colour {} can't move, it is colour {}'s turn", color, current_color)
+            }
+            MoveError::UnknownPiece { piece, orientation } => {
+                write!(f, "there is no piece {} with orientation {}", piece, orientation)
+            }
+            MoveError::PieceAlreadyPlaced { piece } => write!(f, "piece {} has already been placed", piece),
+            MoveError::OutOfBounds(at) => write!(f, "tile ({}, {}) is off the board", at.x, at.y),
+            MoveError::Overlap(at) => write!(f, "tile ({}, {}) is already covered", at.x, at.y),
+            MoveError::TouchesOwnEdge(at) => {
+                write!(f, "tile ({}, {}) shares an edge with a piece of the same colour", at.x, at.y)
+            }
+            MoveError::NoCornerContact(at) => write!(
+                f,
+                "piece placed at ({}, {}) doesn't touch a piece of the same colour at a corner",
+                at.x, at.y
+            ),
+            MoveError::StartNotCovered(at) => write!(f, "the first piece must cover the start tile ({}, {})", at.x, at.y),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// An iterator over every legal placement for one colour, created by [`BlokusRuleset::legal_moves`].
+///
+/// Candidates are found by putting each corner cell of each piece orientation on each of the colour's anchors.
+/// Each orientation's tiles are laid out on the board once, then shifted onto every candidate and checked with whole-board operations,
+/// so the position is never copied, and with a fixed size bit array nothing is allocated.
+/// A placement touching several anchors is only yielded for the first anchor it covers.
+pub struct LegalMoves<'a, GeomT: BoardGeometry, BitsT: BitArray> {
+    game: &'a BlokusRuleset<GeomT, BitsT>,
+    color: usize,
+
+    /// tiles a new piece may not cover: any occupied tile, or a tile sharing an edge with the colour's own pieces
+    blocked: BitBoard<GeomT, BitsT>,
+    anchors: BitBoard<GeomT, BitsT>,
+
+    piece: usize,
+    orientation: usize,
+
+    /// the current orientation's tiles with its top-left corner at (0, 0), `None` if it doesn't fit on the board
+    shape: Option<BitBoard<GeomT, BitsT>>,
+
+    /// anchors the current orientation has been tried on, a placement covering one of these has already been yielded
+    visited_anchors: BitBoard<GeomT, BitsT>,
+    remaining_anchors: BitBoardCells<BitsT>,
+    anchor: Option<Coordinates>,
+    cell: usize,
+}
+
+impl<'a, GeomT: BoardGeometry, BitsT: BitArray> LegalMoves<'a, GeomT, BitsT> {
+    /// Create the iterator, it yields nothing if `color` is not one of the game's colours
+    pub(crate) fn new(game: &'a BlokusRuleset<GeomT, BitsT>, color: usize) -> Self {
+        if color >= game.color_count() {
+            let empty = BitBoard::new(*game.geometry());
+            return Self {
+                game,
+                color,
+                blocked: empty.clone(),
+                remaining_anchors: empty.iter_set(),
+                visited_anchors: empty.clone(),
+                anchors: empty,
+                piece: game.pieces().pieces.len(),
+                orientation: 0,
+                shape: None,
+                anchor: None,
+                cell: 0,
+            };
+        }
+
+        let state = game.color(color);
+        let anchors = state.anchors().and_not(&game.occupied());
+        let mut moves = Self {
+            game,
+            color,
+            blocked: game.occupied() | state.forbidden(),
+            remaining_anchors: anchors.iter_set(),
+            visited_anchors: BitBoard::new(*game.geometry()),
+            anchors,
+            piece: 0,
+            orientation: 0,
+            shape: None,
+            anchor: None,
+            cell: 0,
+        };
+        moves.skip_placed_pieces();
+        moves.start_orientation();
+        moves
+    }
+
+    /// Get the `n`th cell of the current orientation that may be put on an anchor
+    fn candidate_cell(&self, n: usize) -> Option<Coordinates> {
+        let pieces = self.game.pieces();
+        if self.game.color(self.color).is_first_placement() {
+            // any cell may cover a start tile, not just the corners
+            pieces.orientation(self.piece, self.orientation).shape.iter_set().nth(n)
+        } else {
+            pieces.orientation_corners(self.piece, self.orientation).get(n).copied()
+        }
+    }
+
+    /// Move on to the next piece the colour still has, if the current one has already been placed
+    fn skip_placed_pieces(&mut self) {
+        let state = self.game.color(self.color);
+        while self.piece < self.game.pieces().pieces.len() && !state.has_piece(self.piece) {
+            self.piece += 1;
+        }
+    }
+
+    fn start_orientation(&mut self) {
+        self.shape = if self.piece < self.game.pieces().pieces.len() {
+            self.game.piece_footprint(self.piece, self.orientation, Coordinates::zero())
+        } else {
+            None
+        };
+        self.visited_anchors = BitBoard::new(*self.game.geometry());
+        self.remaining_anchors = self.anchors.iter_set();
+        self.anchor = None;
+    }
+
+    fn next_orientation(&mut self) {
+        self.orientation += 1;
+        if self.orientation >= self.game.pieces().count_orientations(self.piece) {
+            self.orientation = 0;
+            self.piece += 1;
+            self.skip_placed_pieces();
+        }
+        self.start_orientation();
+    }
+}
+
+impl<GeomT: BoardGeometry, BitsT: BitArray> Iterator for LegalMoves<'_, GeomT, BitsT> {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.piece >= self.game.pieces().pieces.len() {
+                return None;
+            }
+
+            let Some(shape) = &self.shape else {
+                self.next_orientation();
+                continue;
+            };
+
+            let anchor = match self.anchor {
+                Some(anchor) => anchor,
+                None => match self.remaining_anchors.next() {
+                    Some(anchor) => {
+                        self.anchor = Some(anchor);
+                        self.cell = 0;
+                        anchor
+                    }
+                    None => {
+                        self.next_orientation();
+                        continue;
+                    }
+                },
+            };
+
+            let Some(cell) = self.candidate_cell(self.cell) else {
+                self.visited_anchors.set(anchor, true);
+                self.anchor = None;
+                continue;
+            };
+            self.cell += 1;
+
+            // shifting drops tiles moved off the board, so a placement that doesn't fit loses some
+            let at = Coordinates::new(anchor.x - cell.x, anchor.y - cell.y);
+            let placed = shape.clone().shift(at.x, at.y);
+            if placed.count_ones() != shape.count_ones()
+                || placed.intersects(&self.blocked)
+                || placed.intersects(&self.visited_anchors)
+            {
+                continue;
+            }
+
+            return Some(Move::Place {
+                piece: self.piece as u8,
+                orientation: self.orientation as u8,
+                at,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::Move;
+    use crate::{
+        bitboard::{Coordinates, DynGeometry, StaticGeometry, WordVec},
+        ruleset::{BlokusPieceSet, BlokusRuleset, StandardBlokusPieceSet},
+    };
+
+    fn brute_force_moves(game: &BlokusRuleset<StaticGeometry<7, 7>, u64>) -> HashSet<Move> {
+        let mut moves = HashSet::new();
+        let pieces = game.pieces();
+        for piece in 0..pieces.pieces.len() {
+            for orientation in 0..pieces.count_orientations(piece) {
+                for y in 0..7 {
+                    for x in 0..7 {
+                        let at = Coordinates::new(x, y);
+                        if game.is_legal_placement(game.current_color(), piece, orientation, at) {
+                            moves.insert(Move::Place {
+                                piece: piece as u8,
+                                orientation: orientation as u8,
+                                at,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        moves
+    }
+
+    fn assert_generator_matches_brute_force(game: &BlokusRuleset<StaticGeometry<7, 7>, u64>) {
+        let generated: Vec<Move> = game.legal_moves().collect();
+        let unique: HashSet<Move> = generated.iter().copied().collect();
+        assert_eq!(generated.len(), unique.len(), "generator yielded duplicate moves");
+        assert_eq!(unique, brute_force_moves(game));
+    }
+
+    #[test]
+    fn moves_are_small() {
+        assert!(std::mem::size_of::<Move>() <= 8);
+    }
+
+    #[test]
+    fn generated_moves_match_brute_force() {
+        // the second colour starts in the middle, where any cell of a piece may cover the start tile
+        let mut game: BlokusRuleset<_, u64> = BlokusRuleset::with_start_tiles(
+            StaticGeometry::<7, 7>,
+            StandardBlokusPieceSet::table(),
+            &[Coordinates::new(0, 0), Coordinates::new(3, 3)],
+        );
+        assert_generator_matches_brute_force(&game);
+
+        assert!(game.place_piece(8, 0, Coordinates::new(0, 0)));
+        assert_generator_matches_brute_force(&game);
+
+        // keep playing the last generated move, so later positions have several pieces down for each colour
+        for _ in 0..4 {
+            let Some(Move::Place { piece, orientation, at }) = game.legal_moves().last() else {
+                panic!("expected a legal placement");
+            };
+            assert!(game.place_piece(piece as usize, orientation as usize, at));
+            assert_generator_matches_brute_force(&game);
+        }
+    }
+
+    #[test]
+    fn heap_backed_board_generates_the_same_moves() {
+        let start_tiles = [Coordinates::new(0, 0), Coordinates::new(6, 6)];
+        let mut fixed: BlokusRuleset<_, u64> =
+            BlokusRuleset::with_start_tiles(StaticGeometry::<7, 7>, StandardBlokusPieceSet::table(), &start_tiles);
+        let mut heap: BlokusRuleset<_, WordVec> =
+            BlokusRuleset::with_start_tiles(DynGeometry::new(7, 7), StandardBlokusPieceSet::table(), &start_tiles);
+
+        for _ in 0..4 {
+            let moves: Vec<Move> = fixed.legal_moves().collect();
+            assert_eq!(heap.legal_moves().collect::<Vec<_>>(), moves);
+
+            let Some(&Move::Place { piece, orientation, at }) = moves.first() else {
+                panic!("expected a legal placement");
+            };
+            assert!(fixed.place_piece(piece as usize, orientation as usize, at));
+            assert!(heap.place_piece(piece as usize, orientation as usize, at));
+        }
+    }
+}
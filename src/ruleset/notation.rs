@@ -0,0 +1,310 @@
+use std::{fmt, str::FromStr};
+
+use crate::bitboard::{Coordinates, Transform};
+
+use super::{BlokusPiece, BlokusPieceSet, BlokusPieceTable, Move, StandardBlokusPieceSet};
+
+/// The name of each piece in [`StandardBlokusPieceSet::PIECES`], by index.
+///
+/// Pentominoes use their conventional letter, other pieces add their size to the letter of the pentomino they resemble.
+/// The I and V pentominoes are `I5` and `V5`, so a name followed by a rotation is never another piece's name.
+pub const STANDARD_PIECE_NAMES: [&str; 21] = [
+    "I1", "I2", "I3", "I4", "I5", "L", "V5", "L4", "V3", "X", "F", "T", "Y", "T4", "U", "O4", "P", "Z", "N", "W", "Z4",
+];
+
+/// Find the transform for a piece flipped horizontally if `mirrored`, then turned clockwise `rotation` times
+fn notation_transform(mirrored: bool, rotation: u8) -> Transform {
+    let mut transform = match mirrored {
+        true => Transform::FlipHorizontal,
+        false => Transform::Identity,
+    };
+    for _ in 0..rotation {
+        transform = transform.then(Transform::RotateClockwise);
+    }
+    transform
+}
+
+/// Find the standard piece with the same shape as `shape`, and the simplest way to write that orientation of it,
+/// preferring unmirrored pieces and fewer rotations
+fn standard_notation(shape: &BlokusPiece) -> Option<(usize, bool, u8)> {
+    let standard = StandardBlokusPieceSet::table();
+    standard.pieces.iter().enumerate().find_map(|(piece, standard_piece)| {
+        [false, true]
+            .into_iter()
+            .flat_map(|mirrored| (0..4).map(move |rotation| (mirrored, rotation)))
+            .find(|&(mirrored, rotation)| {
+                standard_piece
+                    .transform(notation_transform(mirrored, rotation))
+                    .same_shape(shape)
+            })
+            .map(|(mirrored, rotation)| (piece, mirrored, rotation))
+    })
+}
+
+/// Write a board column as a letter, continuing with `aa`, `ab`, ... after `z`
+fn write_column(f: &mut fmt::Formatter<'_>, column: usize) -> fmt::Result {
+    if column >= 26 {
+        write_column(f, column / 26 - 1)?;
+    }
+    write!(f, "{}", (b'a' + (column % 26) as u8) as char)
+}
+
+/// A move written in notation for a particular piece table, see [`Move::notation`]
+#[derive(Debug, Clone, Copy)]
+pub struct MoveNotation<'a> {
+    mv: Move,
+    table: &'a BlokusPieceTable,
+}
+
+impl fmt::Display for MoveNotation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Move::Place { piece, orientation, at } = self.mv else {
+            return write!(f, "pass");
+        };
+
+        let (piece, orientation) = (piece as usize, orientation as usize);
+        let known = piece < self.table.pieces.len() && orientation < self.table.count_orientations(piece);
+        match known.then(|| standard_notation(self.table.orientation(piece, orientation))).flatten() {
+            Some((standard_piece, mirrored, rotation)) => {
+                write!(f, "{}", STANDARD_PIECE_NAMES[standard_piece])?;
+                if mirrored {
+                    write!(f, "'")?;
+                }
+                if rotation != 0 {
+                    write!(f, "{}", rotation)?;
+                }
+            }
+            None => write!(f, "?{}.{}", piece, orientation)?,
+        }
+
+        write!(f, " ")?;
+        write_column(f, at.x as usize)?;
+        write!(f, "{}", at.y + 1)
+    }
+}
+
+impl Move {
+    /// Write this move in notation: the piece's name, a `'` if the piece is flipped horizontally,
+    /// the number of clockwise quarter turns (omitted if 0), then the column letter and one-based row number of
+    /// the top-left corner of the piece's bounding box, e.g. `F'3 k10`. Passes are written as `pass`.
+    ///
+    /// Moves only store indexes, so `table` must be the table of the game the move belongs to.
+    /// `Display` and `FromStr` on [`Move`] use the [`StandardBlokusPieceSet`] table.
+    /// Pieces are named after the piece in [`StandardBlokusPieceSet`] with the same shape,
+    /// pieces with no standard shape are written as `?piece.orientation`, which can't be parsed.
+    pub fn notation(self, table: &BlokusPieceTable) -> MoveNotation<'_> {
+        MoveNotation { mv: self, table }
+    }
+
+    /// Parse a move written in notation, see [`Move::notation`], finding the named piece and orientation in `table`
+    pub fn from_notation(text: &str, table: &BlokusPieceTable) -> Result<Move, ParseMoveError> {
+        let text = text.trim();
+        if text == "pass" {
+            return Ok(Move::Pass);
+        }
+
+        let (piece_text, coordinates_text) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+
+        // try longer names first, so `I5` isn't read as `I` rotated 5 times
+        let (standard_piece, orientation_text) = STANDARD_PIECE_NAMES
+            .iter()
+            .enumerate()
+            .filter_map(|(i, name)| Some((i, piece_text.strip_prefix(name)?, name.len())))
+            .max_by_key(|&(_, _, name_len)| name_len)
+            .map(|(i, rest, _)| (i, rest))
+            .ok_or_else(|| ParseMoveError::UnknownPiece(piece_text.to_string()))?;
+
+        let invalid_orientation = || ParseMoveError::InvalidOrientation(orientation_text.to_string());
+        let (mirrored, rotation_text) = match orientation_text.strip_prefix('\'') {
+            Some(rest) => (true, rest),
+            None => (false, orientation_text),
+        };
+        let rotation = match rotation_text {
+            "" => 0,
+            text => text.parse::<u8>().ok().filter(|&r| r < 4).ok_or_else(invalid_orientation)?,
+        };
+
+        let shape = StandardBlokusPieceSet::table().pieces[standard_piece].transform(notation_transform(mirrored, rotation));
+        let (piece, orientation) = (0..table.pieces.len())
+            .flat_map(|piece| (0..table.count_orientations(piece)).map(move |orientation| (piece, orientation)))
+            .find(|&(piece, orientation)| table.orientation(piece, orientation).same_shape(&shape))
+            .ok_or_else(|| ParseMoveError::NotInPieceSet(piece_text.to_string()))?;
+
+        let at = parse_coordinates(coordinates_text.trim())
+            .ok_or_else(|| ParseMoveError::InvalidCoordinates(coordinates_text.trim().to_string()))?;
+
+        Ok(Move::Place {
+            piece: piece as u8,
+            orientation: orientation as u8,
+            at,
+        })
+    }
+}
+
+/// Write a move of a game using [`StandardBlokusPieceSet`], see [`Move::notation`] for other piece sets
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.notation(&StandardBlokusPieceSet::table()))
+    }
+}
+
+/// Parse a move of a game using [`StandardBlokusPieceSet`], see [`Move::from_notation`] for other piece sets
+impl FromStr for Move {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Move::from_notation(s, &StandardBlokusPieceSet::table())
+    }
+}
+
+/// The reason a move could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParseMoveError {
+    /// the move doesn't start with a piece name from [`STANDARD_PIECE_NAMES`]
+    UnknownPiece(String),
+
+    /// the named piece isn't in the game's piece table
+    NotInPieceSet(String),
+
+    /// the text after the piece name isn't an optional `'` followed by an optional rotation from 0 to 3
+    InvalidOrientation(String),
+
+    /// the coordinates are missing, or aren't a column letter followed by a row number
+    InvalidCoordinates(String),
+}
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMoveError::UnknownPiece(text) => write!(f, "unknown piece {:?}", text),
+            ParseMoveError::NotInPieceSet(text) => write!(f, "piece {:?} is not in the piece set", text),
+            ParseMoveError::InvalidOrientation(text) => write!(f, "invalid orientation {:?}", text),
+            ParseMoveError::InvalidCoordinates(text) => write!(f, "invalid coordinates {:?}", text),
+        }
+    }
+}
+
+impl std::error::Error for ParseMoveError {}
+
+fn parse_coordinates(text: &str) -> Option<Coordinates> {
+    let digits_start = text.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = text.split_at(digits_start);
+    if letters.is_empty() || !letters.bytes().all(|b| b.is_ascii_lowercase()) {
+        return None;
+    }
+
+    let column = letters.bytes().try_fold(0i16, |column, b| {
+        column.checked_mul(26)?.checked_add((b - b'a') as i16 + 1)
+    })? - 1;
+    let row = digits.parse::<i16>().ok().filter(|&row| row > 0)? - 1;
+    Some(Coordinates::new(column, row))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ParseMoveError, STANDARD_PIECE_NAMES};
+    use crate::{
+        bitboard::Coordinates,
+        ruleset::{BlokusPieceSet, BlokusRulesetBuilder, LTrominoPieceSet, Move, StandardBlokusPieceSet},
+    };
+
+    #[test]
+    fn every_orientation_round_trips() {
+        let table = StandardBlokusPieceSet::table();
+        for (piece, name) in STANDARD_PIECE_NAMES.iter().enumerate() {
+            for orientation in 0..table.count_orientations(piece) {
+                let mv = Move::Place {
+                    piece: piece as u8,
+                    orientation: orientation as u8,
+                    at: Coordinates::new(piece as i16, 19),
+                };
+                let text = mv.notation(&table).to_string();
+                assert!(text.starts_with(name), "{}", text);
+                assert_eq!(Move::from_notation(&text, &table), Ok(mv), "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn parse_move_notation() {
+        let table = StandardBlokusPieceSet::table();
+        assert_eq!(Move::from_notation("pass", &table), Ok(Move::Pass));
+        assert_eq!(
+            Move::from_notation("I1 a1", &table),
+            Ok(Move::Place {
+                piece: 0,
+                orientation: 0,
+                at: Coordinates::new(0, 0)
+            })
+        );
+        assert_eq!(Move::Pass.notation(&table).to_string(), "pass");
+
+        // the X pentomino looks the same after any transform
+        let x = Move::from_notation("X'3 k10", &table).unwrap();
+        assert_eq!(x.notation(&table).to_string(), "X k10");
+        assert_eq!(
+            x,
+            Move::Place {
+                piece: 9,
+                orientation: 0,
+                at: Coordinates::new(10, 9)
+            }
+        );
+
+        // the I5 pentomino is vertical after a quarter turn
+        let i5 = Move::from_notation("I51 t16", &table).unwrap();
+        assert!(matches!(i5, Move::Place { piece: 4, .. }));
+        assert_eq!(i5.notation(&table).to_string(), "I51 t16");
+
+        let ab = Move::Place {
+            piece: 0,
+            orientation: 0,
+            at: Coordinates::new(27, 0),
+        };
+        assert_eq!(ab.notation(&table).to_string(), "I1 ab1");
+        assert_eq!(Move::from_notation("I1 ab1", &table), Ok(ab));
+    }
+
+    #[test]
+    fn standard_moves_display_and_parse() {
+        let mv: Move = "F'3 k10".parse().unwrap();
+        assert_eq!(Ok(mv), Move::from_notation("F'3 k10", &StandardBlokusPieceSet::table()));
+        assert_eq!(format!("{mv}"), "F'3 k10");
+        assert_eq!(Move::Pass.to_string(), "pass");
+        assert_eq!("pass".parse::<Move>(), Ok(Move::Pass));
+        assert_eq!("Q a1".parse::<Move>(), Err(ParseMoveError::UnknownPiece("Q".to_string())));
+    }
+
+    #[test]
+    fn parse_move_errors() {
+        let table = StandardBlokusPieceSet::table();
+        assert_eq!(Move::from_notation("Q a1", &table), Err(ParseMoveError::UnknownPiece("Q".to_string())));
+        assert_eq!(Move::from_notation("F4 a1", &table), Err(ParseMoveError::InvalidOrientation("4".to_string())));
+        assert_eq!(Move::from_notation("F", &table), Err(ParseMoveError::InvalidCoordinates("".to_string())));
+        assert_eq!(Move::from_notation("F a0", &table), Err(ParseMoveError::InvalidCoordinates("a0".to_string())));
+        assert_eq!(Move::from_notation("F 10", &table), Err(ParseMoveError::InvalidCoordinates("10".to_string())));
+    }
+
+    #[test]
+    fn notation_uses_the_game_table() {
+        let table = LTrominoPieceSet::table();
+        let game = BlokusRulesetBuilder::new(6, 6).pieces(table).build::<u64>().unwrap();
+        let mut names: Vec<String> = game.legal_moves().map(|mv| mv.notation(game.pieces()).to_string()).collect();
+        names.sort();
+        names.dedup();
+        // the fourth orientation leaves a1 empty
+        assert_eq!(names, vec!["V3 a1", "V31 a1", "V33 a1"]);
+
+        for mv in game.legal_moves() {
+            assert_eq!(Move::from_notation(&mv.notation(&table).to_string(), &table), Ok(mv));
+        }
+        assert_eq!(Move::from_notation("I1 a1", &table), Err(ParseMoveError::NotInPieceSet("I1".to_string())));
+
+        let unknown = Move::Place {
+            piece: 1,
+            orientation: 0,
+            at: Coordinates::new(0, 0),
+        };
+        assert_eq!(unknown.notation(&table).to_string(), "?1.0 a1");
+    }
+}
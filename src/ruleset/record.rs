@@ -0,0 +1,520 @@
+use std::{fmt, str::FromStr};
+
+use crate::bitboard::{Coordinates, DynGeometry, WordVec};
+
+use super::{
+    BlokusPieceSet, BlokusPieceTable, BlokusRuleset, BlokusRulesetBuilder, BuildRulesetError, DominoPieceSet,
+    LTetrominoPieceSet, LTrominoPieceSet, MonominoPieceSet, Move, MoveError, PentominoPieceSet, StandardBlokusPieceSet,
+    StartTiles, TetrominoPieceSet,
+};
+
+/// The board layout of a recorded game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordVariant {
+    /// colours start in the corners, with 2 players each playing 2 colours, or 3 players sharing the fourth colour
+    Standard,
+
+    /// two colours starting 4 tiles in from the top-left and bottom-right corners, (4, 4) and (9, 9) on a 14x14 board
+    Duo,
+}
+
+impl RecordVariant {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RecordVariant::Standard => "standard",
+            RecordVariant::Duo => "duo",
+        }
+    }
+}
+
+/// The piece sets a game record can name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordPieceSet {
+    Standard,
+    Monomino,
+    Domino,
+    LTromino,
+    LTetromino,
+    Tetrominoes,
+    Pentominoes,
+}
+
+impl RecordPieceSet {
+    pub const ALL: [RecordPieceSet; 7] = [
+        RecordPieceSet::Standard,
+        RecordPieceSet::Monomino,
+        RecordPieceSet::Domino,
+        RecordPieceSet::LTromino,
+        RecordPieceSet::LTetromino,
+        RecordPieceSet::Tetrominoes,
+        RecordPieceSet::Pentominoes,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RecordPieceSet::Standard => "standard",
+            RecordPieceSet::Monomino => "monomino",
+            RecordPieceSet::Domino => "domino",
+            RecordPieceSet::LTromino => "l-tromino",
+            RecordPieceSet::LTetromino => "l-tetromino",
+            RecordPieceSet::Tetrominoes => "tetrominoes",
+            RecordPieceSet::Pentominoes => "pentominoes",
+        }
+    }
+
+    pub fn table(&self) -> BlokusPieceTable {
+        match self {
+            RecordPieceSet::Standard => StandardBlokusPieceSet::table(),
+            RecordPieceSet::Monomino => MonominoPieceSet::table(),
+            RecordPieceSet::Domino => DominoPieceSet::table(),
+            RecordPieceSet::LTromino => LTrominoPieceSet::table(),
+            RecordPieceSet::LTetromino => LTetrominoPieceSet::table(),
+            RecordPieceSet::Tetrominoes => TetrominoPieceSet::table(),
+            RecordPieceSet::Pentominoes => PentominoPieceSet::table(),
+        }
+    }
+}
+
+/// A move in a game record, with an optional comment
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordedMove {
+    pub mv: Move,
+    pub comment: Option<String>,
+}
+
+/// A complete game: the rules it was played with and every move, including passes.
+///
+/// Records are written as text, a header of `Key: value` lines, then a blank line, then one move per line in
+/// [`Move::notation`]. A `#` starts a comment, which is kept if it follows a move. The optional result goes on a
+/// `Result:` line, which must be the last line with a move or result on it.
+///
+/// ```text
+/// Variant: standard
+/// Players: 4
+/// Pieces: standard
+/// Board: 20x20
+///
+/// I5 a1 # opening along the edge
+/// pass
+/// Result: 1-0
+/// ```
+///
+/// Moves are written with the names of [`StandardBlokusPieceSet`]'s pieces, even when the record uses a smaller set.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameRecord {
+    variant: RecordVariant,
+    players: usize,
+    pieces: RecordPieceSet,
+    width: i16,
+    height: i16,
+
+    /// moves in order, using indexes into `pieces`' table, see [`GameRecord::push`]
+    moves: Vec<RecordedMove>,
+    result: Option<String>,
+}
+
+/// The reason a game record could not be read or replayed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GameRecordError {
+    /// a required header line is missing
+    MissingHeader(&'static str),
+
+    /// a header line couldn't be read, `line` is one-based
+    InvalidHeader { line: usize, text: String },
+
+    /// a move couldn't be read, or uses a piece that isn't in the record's piece set, `line` is one-based
+    InvalidMove { line: usize, text: String },
+
+    /// a move or a second result follows the `Result:` line, `line` is one-based
+    AfterResult { line: usize, text: String },
+
+    /// a comment contains a line break, so it can't be written on the move's line
+    InvalidComment(String),
+
+    /// a result contains a line break or a `#`, so it can't be written on the `Result:` line
+    InvalidResult(String),
+
+    /// the header describes a game that can't be set up
+    InvalidRules(BuildRulesetError),
+
+    /// the move at `index` in the move list is not legal
+    IllegalMove { index: usize, error: MoveError },
+}
+
+impl fmt::Display for GameRecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameRecordError::MissingHeader(key) => write!(f, "missing header {:?}", key),
+            GameRecordError::InvalidHeader { line, text } => write!(f, "invalid header {:?} on line {}", text, line),
+            GameRecordError::InvalidMove { line, text } => write!(f, "invalid move {:?} on line {}", text, line),
+            GameRecordError::AfterResult { line, text } => {
+                write!(f, "{:?} on line {} follows the result", text, line)
+            }
+            GameRecordError::InvalidComment(comment) => write!(f, "comment {:?} can't be written on one line", comment),
+            GameRecordError::InvalidResult(result) => write!(f, "result {:?} can't be written on one line", result),
+            GameRecordError::InvalidRules(error) => write!(f, "invalid rules: {}", error),
+            GameRecordError::IllegalMove { index, error } => write!(f, "move {} is illegal: {}", index + 1, error),
+        }
+    }
+}
+
+impl std::error::Error for GameRecordError {}
+
+impl GameRecord {
+    pub fn new(variant: RecordVariant, players: usize, pieces: RecordPieceSet, width: i16, height: i16) -> Self {
+        Self {
+            variant,
+            players,
+            pieces,
+            width,
+            height,
+            moves: Vec::new(),
+            result: None,
+        }
+    }
+
+    pub fn variant(&self) -> RecordVariant {
+        self.variant
+    }
+
+    pub fn players(&self) -> usize {
+        self.players
+    }
+
+    pub fn pieces(&self) -> RecordPieceSet {
+        self.pieces
+    }
+
+    pub fn width(&self) -> i16 {
+        self.width
+    }
+
+    pub fn height(&self) -> i16 {
+        self.height
+    }
+
+    /// Moves in order, using indexes into the record's piece table, see [`GameRecord::push`]
+    pub fn moves(&self) -> &[RecordedMove] {
+        &self.moves
+    }
+
+    pub fn result(&self) -> Option<&str> {
+        self.result.as_deref()
+    }
+
+    /// Set the game's result, e.g. `1-0`.
+    /// Surrounding whitespace is removed, and the result may not contain a line break or a `#`, so it reads back the same.
+    pub fn set_result(&mut self, result: Option<String>) -> Result<(), GameRecordError> {
+        let result = result.map(|result| result.trim().to_string());
+        if let Some(result) = result.as_ref().filter(|result| result.contains(['\n', '\r', '#'])) {
+            return Err(GameRecordError::InvalidResult(result.clone()));
+        }
+
+        self.result = result;
+        Ok(())
+    }
+
+    /// Add a move to the end of the record.
+    /// The move must use a piece and orientation from the record's piece set, so it can be written in notation,
+    /// but it isn't checked against the rules until the record is replayed.
+    ///
+    /// Surrounding whitespace is removed from the comment, and it may not contain a line break, so it reads back the same.
+    pub fn push(&mut self, mv: Move, comment: Option<String>) -> Result<(), GameRecordError> {
+        let comment = comment.map(|comment| comment.trim().to_string());
+        if let Some(comment) = comment.as_ref().filter(|comment| comment.contains(['\n', '\r'])) {
+            return Err(GameRecordError::InvalidComment(comment.clone()));
+        }
+
+        if let Move::Place { piece, orientation, .. } = mv {
+            let table = self.pieces.table();
+            let (piece, orientation) = (piece as usize, orientation as usize);
+            if piece >= table.pieces.len() || orientation >= table.count_orientations(piece) {
+                return Err(GameRecordError::IllegalMove {
+                    index: self.moves.len(),
+                    error: MoveError::UnknownPiece { piece, orientation },
+                });
+            }
+        }
+
+        self.moves.push(RecordedMove { mv, comment });
+        Ok(())
+    }
+
+    /// Set up the game described by the header, before any moves
+    pub fn new_game(&self) -> Result<BlokusRuleset<DynGeometry, WordVec>, GameRecordError> {
+        let mut builder = BlokusRulesetBuilder::new(self.width, self.height)
+            .players(self.players)
+            .pieces(self.pieces.table());
+        builder = match (self.variant, self.players) {
+            (RecordVariant::Standard, 2) => builder.colors_per_player(2),
+            (RecordVariant::Standard, 3) => builder.shared_colors(1),
+            (RecordVariant::Standard, _) => builder,
+            (RecordVariant::Duo, _) => builder.start_tiles(vec![
+                StartTiles::Tile(Coordinates::new(4, 4)),
+                StartTiles::Tile(Coordinates::new(self.width - 5, self.height - 5)),
+            ]),
+        };
+        builder.build().map_err(GameRecordError::InvalidRules)
+    }
+
+    /// Play every move from the start of the game, checking each one is legal
+    pub fn replay(&self) -> Result<BlokusRuleset<DynGeometry, WordVec>, GameRecordError> {
+        let mut game = self.new_game()?;
+        for (index, recorded) in self.moves.iter().enumerate() {
+            game.validate_move(game.current_color(), recorded.mv)
+                .map_err(|error| GameRecordError::IllegalMove { index, error })?;
+            game.apply(recorded.mv);
+        }
+        Ok(game)
+    }
+}
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Variant: {}", self.variant.name())?;
+        writeln!(f, "Players: {}", self.players)?;
+        writeln!(f, "Pieces: {}", self.pieces.name())?;
+        writeln!(f, "Board: {}x{}", self.width, self.height)?;
+        writeln!(f)?;
+
+        let table = self.pieces.table();
+        for recorded in &self.moves {
+            write!(f, "{}", recorded.mv.notation(&table))?;
+            if let Some(comment) = &recorded.comment {
+                write!(f, " # {}", comment)?;
+            }
+            writeln!(f)?;
+        }
+
+        if let Some(result) = &self.result {
+            writeln!(f, "Result: {}", result)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = GameRecordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        let (mut variant, mut players, mut pieces, mut board) = (None, None, None, None);
+        for (line, text) in lines.by_ref() {
+            let text = text.trim();
+            if text.is_empty() {
+                break;
+            }
+            if text.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || GameRecordError::InvalidHeader {
+                line,
+                text: text.to_string(),
+            };
+            let (key, value) = text.split_once(':').ok_or_else(invalid)?;
+            let value = value.trim();
+            match key.trim() {
+                "Variant" => {
+                    variant = Some(match value {
+                        "standard" => RecordVariant::Standard,
+                        "duo" => RecordVariant::Duo,
+                        _ => return Err(invalid()),
+                    })
+                }
+                "Players" => players = Some(value.parse::<usize>().map_err(|_| invalid())?),
+                "Pieces" => {
+                    pieces = Some(RecordPieceSet::ALL.into_iter().find(|p| p.name() == value).ok_or_else(invalid)?)
+                }
+                "Board" => {
+                    let (width, height) = value.split_once('x').ok_or_else(invalid)?;
+                    let parse_size = |size: &str| size.parse::<i16>().ok().filter(|&size| size > 0).ok_or_else(invalid);
+                    board = Some((parse_size(width)?, parse_size(height)?))
+                }
+                _ => return Err(invalid()),
+            }
+        }
+
+        let (width, height) = board.ok_or(GameRecordError::MissingHeader("Board"))?;
+        let mut record = GameRecord::new(
+            variant.ok_or(GameRecordError::MissingHeader("Variant"))?,
+            players.ok_or(GameRecordError::MissingHeader("Players"))?,
+            pieces.ok_or(GameRecordError::MissingHeader("Pieces"))?,
+            width,
+            height,
+        );
+
+        let table = record.pieces.table();
+        for (line, text) in lines {
+            let (move_text, comment) = match text.split_once('#') {
+                Some((move_text, comment)) => (move_text.trim(), Some(comment.trim().to_string())),
+                None => (text.trim(), None),
+            };
+            if move_text.is_empty() {
+                continue;
+            }
+            if record.result.is_some() {
+                return Err(GameRecordError::AfterResult {
+                    line,
+                    text: move_text.to_string(),
+                });
+            }
+            if let Some(result) = move_text.strip_prefix("Result:") {
+                record.result = Some(result.trim().to_string());
+                continue;
+            }
+
+            let mv = Move::from_notation(move_text, &table).map_err(|_| GameRecordError::InvalidMove {
+                line,
+                text: move_text.to_string(),
+            })?;
+            record.push(mv, comment)?;
+        }
+
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GameRecord, GameRecordError, RecordPieceSet, RecordVariant};
+    use crate::{
+        bitboard::Coordinates,
+        ruleset::{Move, MoveError},
+    };
+
+    #[test]
+    fn write_read_and_replay() {
+        let mut record = GameRecord::new(RecordVariant::Duo, 2, RecordPieceSet::Standard, 14, 14);
+        let mut game = record.new_game().unwrap();
+        for turn in 0..6 {
+            let mv = game.legal_moves().next().unwrap();
+            game.apply(mv);
+            record.push(mv, (turn == 0).then(|| "first move".to_string())).unwrap();
+        }
+        record.push(Move::Pass, None).unwrap();
+        game.apply(Move::Pass);
+        record.set_result(Some("unfinished".to_string())).unwrap();
+
+        let text = record.to_string();
+        assert!(text.starts_with("Variant: duo\nPlayers: 2\nPieces: standard\nBoard: 14x14\n\n"));
+        assert!(text.contains(" # first move\n"));
+        assert!(text.ends_with("pass\nResult: unfinished\n"));
+
+        let read: GameRecord = text.parse().unwrap();
+        assert_eq!(read, record);
+        assert_eq!(read.replay().unwrap(), game);
+    }
+
+    #[test]
+    fn small_piece_sets_use_standard_names() {
+        let record: GameRecord = "Variant: standard\nPlayers: 2\nPieces: tetrominoes\nBoard: 8x8\n\nO4 a1\nI4 e1\n"
+            .parse()
+            .unwrap();
+        assert!(matches!(record.moves()[1].mv, Move::Place { piece: 0, .. }));
+        let game = record.replay().unwrap();
+        assert_eq!(game.color_count(), 4);
+        assert_eq!(game.occupied().count_ones(), 8);
+
+        let bad = "Variant: standard\nPlayers: 2\nPieces: tetrominoes\nBoard: 8x8\n\nF a1\n".parse::<GameRecord>();
+        assert_eq!(
+            bad,
+            Err(GameRecordError::InvalidMove {
+                line: 6,
+                text: "F a1".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn replay_rejects_illegal_moves() {
+        let record: GameRecord = "Variant: standard\nPlayers: 4\nPieces: standard\nBoard: 20x20\n\nI1 a1\nI1 b2\n"
+            .parse()
+            .unwrap();
+        assert!(matches!(
+            record.replay(),
+            Err(GameRecordError::IllegalMove {
+                index: 1,
+                error: MoveError::StartNotCovered(_)
+            })
+        ));
+
+        assert_eq!(
+            "Variant: standard\nPlayers: 4\n\n".parse::<GameRecord>(),
+            Err(GameRecordError::MissingHeader("Board"))
+        );
+        assert_eq!(
+            "Variant: standard\nPlayers: 4\nPieces: standard\nBoard: -1x5\n\n".parse::<GameRecord>(),
+            Err(GameRecordError::InvalidHeader {
+                line: 4,
+                text: "Board: -1x5".to_string()
+            })
+        );
+        assert_eq!(
+            "Variant: classic\n".parse::<GameRecord>(),
+            Err(GameRecordError::InvalidHeader {
+                line: 1,
+                text: "Variant: classic".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn push_rejects_moves_that_cannot_be_written() {
+        let mut record = GameRecord::new(RecordVariant::Standard, 4, RecordPieceSet::Monomino, 20, 20);
+        let mv = Move::Place {
+            piece: 0,
+            orientation: 1,
+            at: Coordinates::new(0, 0),
+        };
+        assert_eq!(
+            record.push(mv, None),
+            Err(GameRecordError::IllegalMove {
+                index: 0,
+                error: MoveError::UnknownPiece { piece: 0, orientation: 1 }
+            })
+        );
+        assert!(record.moves().is_empty());
+    }
+
+    #[test]
+    fn comments_and_results_read_back_the_same() {
+        let mut record = GameRecord::new(RecordVariant::Standard, 4, RecordPieceSet::Standard, 20, 20);
+        record.push(Move::Pass, Some(" a # inside a comment ".to_string())).unwrap();
+        assert_eq!(record.moves()[0].comment.as_deref(), Some("a # inside a comment"));
+        assert_eq!(
+            record.push(Move::Pass, Some("two\nlines".to_string())),
+            Err(GameRecordError::InvalidComment("two\nlines".to_string()))
+        );
+        assert_eq!(
+            record.set_result(Some("1-0 # resigned".to_string())),
+            Err(GameRecordError::InvalidResult("1-0 # resigned".to_string()))
+        );
+        assert_eq!(record.set_result(Some("1-0\r\n".to_string())), Ok(()));
+        assert_eq!(record.result(), Some("1-0"));
+        assert_eq!(record.to_string().parse(), Ok(record));
+    }
+
+    #[test]
+    fn result_must_be_last() {
+        let header = "Variant: standard\nPlayers: 4\nPieces: standard\nBoard: 20x20\n\n";
+        let record: GameRecord = format!("{header}pass\nResult: 0-1\n# a comment after the result\n").parse().unwrap();
+        assert_eq!(record.result(), Some("0-1"));
+
+        assert_eq!(
+            format!("{header}Result: 0-1\npass\n").parse::<GameRecord>(),
+            Err(GameRecordError::AfterResult {
+                line: 7,
+                text: "pass".to_string()
+            })
+        );
+        assert_eq!(
+            format!("{header}Result: 0-1\nResult: 1-0\n").parse::<GameRecord>(),
+            Err(GameRecordError::AfterResult {
+                line: 7,
+                text: "Result: 1-0".to_string()
+            })
+        );
+    }
+}
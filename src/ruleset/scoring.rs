@@ -0,0 +1,127 @@
+use crate::bitboard::{BitArray, BoardGeometry};
+
+use super::{BlokusRuleset, ColorOwner};
+
+/// How a finished game is scored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ScoringRule {
+    /// -1 for each square of each piece a colour didn't place
+    #[default]
+    Basic,
+
+    /// Like [`ScoringRule::Basic`], but a colour that placed all of its pieces gets +15, or +20 if the monomino was placed last
+    Advanced,
+}
+
+impl ScoringRule {
+    pub const ALL_PIECES_BONUS: i32 = 15;
+    pub const MONOMINO_LAST_BONUS: i32 = 5;
+}
+
+impl<GeomT: BoardGeometry, BitsT: BitArray> BlokusRuleset<GeomT, BitsT> {
+    /// Count the squares of the pieces `color` has yet to place
+    pub fn remaining_squares(&self, color: usize) -> u32 {
+        let remaining_pieces = self.color(color).remaining_pieces();
+        self.pieces()
+            .pieces
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| remaining_pieces & (1 << i) != 0)
+            .map(|(_, piece)| piece.shape.count_ones())
+            .sum()
+    }
+
+    /// Score a single colour
+    pub fn color_score(&self, color: usize, rule: ScoringRule) -> i32 {
+        let state = self.color(color);
+        let mut score = -(self.remaining_squares(color) as i32);
+        if let (ScoringRule::Advanced, Some(final_piece)) = (rule, state.final_piece()) {
+            score += ScoringRule::ALL_PIECES_BONUS;
+            if self.pieces().pieces[final_piece].shape.count_ones() == 1 {
+                score += ScoringRule::MONOMINO_LAST_BONUS;
+            }
+        }
+        score
+    }
+
+    /// Score each player, adding up the scores of every colour they control. Shared colours aren't scored.
+    pub fn player_scores(&self, rule: ScoringRule) -> Vec<i32> {
+        let mut scores = vec![0; self.player_count()];
+        for color in 0..self.color_count() {
+            if let ColorOwner::Player(player) = self.color_owner(color) {
+                scores[player] += self.color_score(color, rule);
+            }
+        }
+        scores
+    }
+
+    /// Rank each player by score, 1 is first place.
+    /// Tied players share a rank and the following ranks are skipped, so scores of 5, 5 and 3 are ranked 1, 1 and 3.
+    pub fn player_ranks(&self, rule: ScoringRule) -> Vec<usize> {
+        let scores = self.player_scores(rule);
+        scores
+            .iter()
+            .map(|score| 1 + scores.iter().filter(|other| *other > score).count())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ScoringRule;
+    use crate::{
+        bitboard::{Coordinates, StaticGeometry},
+        ruleset::{BlokusPieceSet, BlokusRuleset, DominoPieceSet, MonominoPieceSet, StandardBlokus},
+    };
+
+    #[test]
+    fn basic_scores_count_remaining_squares() {
+        let mut game = StandardBlokus::standard();
+        assert_eq!(game.color_score(0, ScoringRule::Basic), -89);
+
+        assert!(game.place_piece(4, 0, Coordinates::new(0, 0)));
+        assert!(game.place_piece(0, 0, Coordinates::new(19, 0)));
+        assert_eq!(game.player_scores(ScoringRule::Basic), vec![-84, -88, -89, -89]);
+        assert_eq!(game.player_scores(ScoringRule::Advanced), vec![-84, -88, -89, -89]);
+        assert_eq!(game.player_ranks(ScoringRule::Basic), vec![1, 2, 3, 3]);
+    }
+
+    #[test]
+    fn shared_and_multiple_colours() {
+        let mut game = StandardBlokus::three_player();
+        assert!(game.place_piece(0, 0, Coordinates::new(0, 0)));
+        assert!(game.place_piece(0, 0, Coordinates::new(19, 0)));
+        assert!(game.place_piece(0, 0, Coordinates::new(19, 19)));
+        assert!(game.place_piece(4, 0, Coordinates::new(0, 19)));
+        assert_eq!(game.player_scores(ScoringRule::Basic), vec![-88, -88, -88]);
+
+        let mut game = StandardBlokus::two_player();
+        assert!(game.place_piece(0, 0, Coordinates::new(0, 0)));
+        assert!(game.place_piece(4, 0, Coordinates::new(15, 0)));
+        assert!(game.place_piece(1, 0, Coordinates::new(18, 19)));
+        assert_eq!(game.player_scores(ScoringRule::Basic), vec![-88 - 87, -84 - 89]);
+        assert_eq!(game.player_ranks(ScoringRule::Basic), vec![2, 1]);
+    }
+
+    #[test]
+    fn advanced_scores_reward_placing_every_piece() {
+        let mut game: BlokusRuleset<_, u32> = BlokusRuleset::with_start_tiles(
+            StaticGeometry::<4, 4>,
+            MonominoPieceSet::table(),
+            &[Coordinates::new(0, 0), Coordinates::new(3, 3)],
+        );
+        assert!(game.place_piece(0, 0, Coordinates::new(0, 0)));
+        assert_eq!(game.player_scores(ScoringRule::Basic), vec![0, -1]);
+        assert_eq!(game.player_scores(ScoringRule::Advanced), vec![20, -1]);
+
+        let mut game: BlokusRuleset<_, u32> = BlokusRuleset::with_start_tiles(
+            StaticGeometry::<4, 4>,
+            DominoPieceSet::table(),
+            &[Coordinates::new(0, 0), Coordinates::new(3, 3)],
+        );
+        assert!(game.place_piece(0, 0, Coordinates::new(0, 0)));
+        assert!(game.place_piece(0, 0, Coordinates::new(2, 3)));
+        assert_eq!(game.player_scores(ScoringRule::Advanced), vec![15, 15]);
+        assert_eq!(game.player_ranks(ScoringRule::Advanced), vec![1, 1]);
+    }
+}
@@ -0,0 +1,284 @@
+//! JavaScript bindings for the demo, mirroring the `Blokus` class from `cgt.js`.
+//!
+//! The demo's `board.moves(polyominos)` becomes `polyominos.flatMap(p => board.moves(p))`,
+//! since exported types can't be borrowed from a JS array.
+//! Its `solve(board, polyominos)` always gives a nimber, which `board.nimValue(...)` computes directly.
+//!
+//! The crate is built as an rlib by default, pass `--crate-type cdylib` to get a module wasm-bindgen can load:
+//! `cargo rustc --lib --release --crate-type cdylib --target wasm32-unknown-unknown --features wasm`
+
+use std::fmt;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    bitboard::{Coordinates, WordVec},
+    bitmap::{BlokusBitmap, TileState},
+    ruleset::{BlokusPieceSet, BlokusPiece, Move, StandardBlokus, StandardBlokusPieceSet},
+};
+
+fn tile_state_name(state: TileState) -> &'static str {
+    match state {
+        TileState::Interior => "interior",
+        TileState::Corner => "corner",
+        TileState::Side => "side",
+        TileState::Empty => "empty",
+    }
+}
+
+/// An argument from JS that was rejected before reaching the bitmap, becomes a JS `Error` through `?`
+///
+/// `JsError` can only be built inside a wasm module, so the checks return this to keep them testable on the host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InvalidArgument(String);
+
+impl fmt::Display for InvalidArgument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for InvalidArgument {}
+
+fn tile_state_from_name(name: &str) -> Result<TileState, InvalidArgument> {
+    match name {
+        "interior" => Ok(TileState::Interior),
+        "corner" => Ok(TileState::Corner),
+        "side" => Ok(TileState::Side),
+        "empty" => Ok(TileState::Empty),
+        _ => Err(InvalidArgument(format!("invalid state: {:?}", name))),
+    }
+}
+
+/// Panics trap the whole wasm module, so sizes and coordinates from JS are checked before they reach the bitmap
+fn check_size(width: i16, height: i16) -> Result<(), InvalidArgument> {
+    match width >= 0 && height >= 0 {
+        true => Ok(()),
+        false => Err(InvalidArgument(format!("invalid size: {}x{}", width, height))),
+    }
+}
+
+/// Check a bitmap's text has no more rows or columns than fit in an `i16`, counting rows the way [`BlokusBitmap`]'s `FromStr` does
+fn check_text_size(s: &str) -> Result<(), InvalidArgument> {
+    let rows = s.lines().map(str::trim).filter(|r| !r.is_empty());
+    let (height, width) = rows.fold((0, 0), |(height, width), row| (height + 1, width.max(row.chars().count())));
+    match width <= i16::MAX as usize && height <= i16::MAX as usize {
+        true => Ok(()),
+        false => Err(InvalidArgument(format!("bitmap too large: {}x{}", width, height))),
+    }
+}
+
+/// A single player position or polyomino, the `Blokus` class from `cgt.js`
+#[wasm_bindgen(js_name = Blokus)]
+#[derive(Clone)]
+pub struct JsBlokus(BlokusBitmap<WordVec>);
+
+#[wasm_bindgen(js_class = Blokus)]
+impl JsBlokus {
+    pub fn empty(width: i16, height: i16) -> Result<JsBlokus, JsError> {
+        check_size(width, height)?;
+        Ok(JsBlokus(BlokusBitmap::empty(width, height)))
+    }
+
+    #[wasm_bindgen(js_name = fromString)]
+    pub fn from_string(s: &str) -> Result<JsBlokus, JsError> {
+        check_text_size(s)?;
+        Ok(JsBlokus(s.parse()?))
+    }
+
+    /// Create a polyomino from one of the standard pieces, see [`StandardBlokusPieceSet`]
+    #[wasm_bindgen(js_name = fromStandardPiece)]
+    pub fn from_standard_piece(piece: usize) -> Result<JsBlokus, JsError> {
+        let piece: &BlokusPiece = StandardBlokusPieceSet::table()
+            .pieces
+            .get(piece)
+            .ok_or_else(|| InvalidArgument(format!("invalid piece index: {}", piece)))?;
+        Ok(JsBlokus(BlokusBitmap::from_piece(piece)))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> i16 {
+        self.0.width()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> i16 {
+        self.0.height()
+    }
+
+    /// Get a tile's state, one of the `TileState` strings: `"interior"`, `"corner"`, `"side"` or `"empty"`
+    pub fn get(&self, x: i16, y: i16) -> Result<String, JsError> {
+        let coord = self.checked_coordinates(x, y)?;
+        Ok(tile_state_name(self.0.get(coord)).to_string())
+    }
+
+    pub fn set(&mut self, x: i16, y: i16, state: &str) -> Result<(), JsError> {
+        let coord = self.checked_coordinates(x, y)?;
+        self.0.set(coord, tile_state_from_name(state)?);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = tryPlacePolyomino)]
+    pub fn try_place_polyomino(&mut self, x: i16, y: i16, polyomino: &JsBlokus, poly_x: i16, poly_y: i16) -> bool {
+        self.0
+            .try_place_polyomino(Coordinates::new(x, y), &polyomino.0, Coordinates::new(poly_x, poly_y))
+    }
+
+    pub fn moves(&self, polyomino: &JsBlokus) -> Vec<JsBlokus> {
+        self.0.moves(std::slice::from_ref(&polyomino.0)).into_iter().map(JsBlokus).collect()
+    }
+
+    /// The value the demo's `solve(board, polyominos)` computes, as `n` for the nimber `*n`, see [`BlokusBitmap::nim_value`]
+    ///
+    /// Exported objects passed in an array are moved into wasm, so pass copies: `board.nimValue(polyominos.map(p => p.clone()))`.
+    #[wasm_bindgen(js_name = nimValue)]
+    pub fn nim_value(&self, polyominos: Vec<JsBlokus>) -> u32 {
+        let polyominos: Vec<_> = polyominos.into_iter().map(|p| p.0).collect();
+        self.0.nim_value(&polyominos)
+    }
+
+    #[wasm_bindgen(js_name = isEqualTo)]
+    pub fn is_equal_to(&self, other: &JsBlokus) -> bool {
+        self.0 == other.0
+    }
+
+    #[wasm_bindgen(js_name = toStringBoard)]
+    pub fn to_string_board(&self) -> String {
+        self.0.to_string()
+    }
+
+    #[wasm_bindgen(js_name = countInterior)]
+    pub fn count_interior(&self) -> u32 {
+        self.0.count_interior()
+    }
+
+    pub fn resize(&self, width: i16, height: i16) -> Result<JsBlokus, JsError> {
+        check_size(width, height)?;
+        Ok(JsBlokus(self.0.resize(width, height)))
+    }
+
+    #[wasm_bindgen(js_name = rotateClockwise)]
+    pub fn rotate_clockwise(&self) -> JsBlokus {
+        JsBlokus(self.0.rotate_clockwise())
+    }
+
+    #[wasm_bindgen(js_name = clone)]
+    pub fn clone_js(&self) -> JsBlokus {
+        self.clone()
+    }
+}
+
+impl JsBlokus {
+    fn checked_coordinates(&self, x: i16, y: i16) -> Result<Coordinates, InvalidArgument> {
+        match (0..self.0.width()).contains(&x) && (0..self.0.height()).contains(&y) {
+            true => Ok(Coordinates::new(x, y)),
+            false => Err(InvalidArgument(format!("({}, {}) is off the board", x, y))),
+        }
+    }
+}
+
+/// A standard four colour game, with moves written in [`Move::notation`]
+#[wasm_bindgen(js_name = Game)]
+pub struct JsGame(StandardBlokus);
+
+#[wasm_bindgen(js_class = Game)]
+impl JsGame {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> JsGame {
+        JsGame(StandardBlokus::standard())
+    }
+
+    #[wasm_bindgen(getter, js_name = currentColor)]
+    pub fn current_color(&self) -> usize {
+        self.0.current_color()
+    }
+
+    /// Every legal move for the current colour
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&self) -> Vec<String> {
+        self.0.legal_moves().map(|mv| mv.notation(self.0.pieces()).to_string()).collect()
+    }
+
+    /// Check and play a move for the current colour, returning the number of colours that passed automatically after it
+    pub fn play(&mut self, notation: &str) -> Result<usize, JsError> {
+        let mv = Move::from_notation(notation, self.0.pieces())?;
+        Ok(self.0.play(mv)?)
+    }
+
+    #[wasm_bindgen(js_name = isGameOver)]
+    pub fn is_game_over(&self) -> bool {
+        self.0.is_game_over()
+    }
+}
+
+impl Default for JsGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_size, check_text_size, tile_state_from_name, JsBlokus, JsGame};
+
+    #[test]
+    fn blokus_reads_and_writes_tiles() {
+        let mut board = JsBlokus::from_string("c.\n..").unwrap();
+        assert_eq!((board.width(), board.height()), (2, 2));
+        assert_eq!(board.get(0, 0).unwrap(), "corner");
+
+        board.set(1, 1, "side").unwrap();
+        assert_eq!(board.get(1, 1).unwrap(), "side");
+        assert_eq!(board.to_string_board(), "c.\n.s\n");
+        assert!(board.clone_js().is_equal_to(&board));
+    }
+
+    #[test]
+    fn blokus_places_polyominoes() {
+        let monomino = JsBlokus::from_string("csc\nsis\ncsc").unwrap();
+        let mut board = JsBlokus::empty(3, 3).unwrap();
+        board.set(0, 0, "corner").unwrap();
+
+        let moves = board.moves(&monomino);
+        assert_eq!(moves.len(), 4);
+        assert!(moves.iter().all(|mv| mv.count_interior() == 1));
+
+        assert!(!board.try_place_polyomino(i16::MIN, 0, &monomino, 1, 1));
+        assert!(!board.try_place_polyomino(0, i16::MIN, &monomino, 1, 1));
+        assert!(board.try_place_polyomino(0, 0, &monomino, 1, 1));
+        assert_eq!(board.to_string_board(), "is.\nsc.\n...\n");
+        assert_eq!(board.nim_value(vec![monomino.clone()]), 0);
+        assert_eq!(JsBlokus::empty(3, 3).unwrap().nim_value(vec![monomino]), 0);
+    }
+
+    #[test]
+    fn blokus_checks_arguments() {
+        let board = JsBlokus::empty(2, 3).unwrap();
+        assert!(board.checked_coordinates(1, 2).is_ok());
+        assert!(board.checked_coordinates(2, 0).is_err());
+        assert!(board.checked_coordinates(0, -1).is_err());
+
+        assert!(tile_state_from_name("corner").is_ok());
+        assert!(tile_state_from_name("Corner").is_err());
+
+        assert!(check_size(0, 0).is_ok());
+        assert!(check_size(-1, 2).is_err());
+
+        let widest_row = ".".repeat(i16::MAX as usize);
+        assert!(check_text_size(&widest_row).is_ok());
+        assert!(check_text_size(&format!("{}.", widest_row)).is_err());
+        assert!(check_text_size(&".\n".repeat(i16::MAX as usize + 1)).is_err());
+    }
+
+    #[test]
+    fn game_plays_legal_moves() {
+        let mut game = JsGame::new();
+        assert_eq!(game.current_color(), 0);
+
+        let moves = game.legal_moves();
+        assert!(!moves.is_empty());
+        assert_eq!(game.play(&moves[0]).unwrap(), 0);
+        assert_eq!(game.current_color(), 1);
+        assert!(!game.is_game_over());
+    }
+}